@@ -0,0 +1,647 @@
+// Copyright (c) 2018 FaultyRAM
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the
+// MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at
+// your option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(feature = "std")]
+use bindgen_openal_sys::ALvoid;
+use bindgen_openal_sys::{
+    alBufferData, alBufferi, alBufferiv, alDeleteBuffers, alGenBuffers, alGetBufferi,
+    alGetBufferiv, ALenum, ALint, ALsizei, ALuint, AL_BITS, AL_CHANNELS, AL_FREQUENCY, AL_SIZE,
+};
+use context::Context;
+#[cfg(feature = "std")]
+use convert;
+use core::cell::Cell;
+use core::convert::TryFrom;
+#[cfg(feature = "std")]
+use core::fmt::{self, Debug, Formatter};
+use core::ptr;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use core::{mem, slice};
+use error::Error;
+#[cfg(feature = "std")]
+use ext::BufferCallbackFn;
+use ext::{
+    BufferStorageFn, BufferSubDataFn, FlushMappedBufferFn, MapBufferFn, UnmapBufferFn,
+    AL_AMBISONIC_LAYOUT_SOFT, AL_AMBISONIC_SCALING_SOFT, AL_LOOP_POINTS_SOFT,
+    AL_UNPACK_AMBISONIC_ORDER_SOFT, AL_UNPACK_BLOCK_ALIGNMENT_SOFT,
+};
+#[cfg(feature = "std")]
+use format;
+use format::{AmbisonicLayout, AmbisonicScaling, ChannelLayout, Format, Sample, SampleType};
+use map::{BufferMap, MapFlags};
+#[cfg(feature = "std")]
+use std::boxed::Box;
+#[cfg(feature = "std")]
+use std::fs;
+#[cfg(feature = "std")]
+use std::panic::{self, AssertUnwindSafe};
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+#[cfg(feature = "std")]
+use stream::Decoder;
+use util;
+use wav::Wav;
+
+#[cfg(feature = "std")]
+/// A software decoder from 8-bit companded samples to 16-bit PCM.
+type G711Decoder = fn(&[u8], &mut [i16]);
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+/// A description of the audio data held by a buffer.
+pub struct BufferInfo {
+    /// The format of the audio data.
+    pub format: Format,
+    /// The format the audio data was uploaded in.
+    ///
+    /// This differs from `format` when the data was converted to 16-bit PCM because the context
+    /// couldn't play it directly.
+    pub original_format: Format,
+    /// The sample rate of the audio data, in hertz.
+    pub frequency: u32,
+    /// The size of the audio data, in bytes.
+    pub size: usize,
+    /// The length of the audio data, in frames.
+    pub frames: usize,
+}
+
+impl BufferInfo {
+    /// Returns the playback duration of the audio data.
+    pub fn duration(&self) -> Duration {
+        if self.frequency == 0 {
+            return Duration::from_secs(0);
+        }
+        let (frames, frequency) = (self.frames as u64, u64::from(self.frequency));
+        let nanos = frames % frequency * 1_000_000_000 / frequency;
+        Duration::new(frames / frequency, nanos as u32)
+    }
+
+    /// Pairs this description with a copy of the buffer's audio data, such as to export it with
+    /// `Wav::write_to`.
+    ///
+    /// Buffers don't keep their data readable, so `data` must be the bytes originally uploaded.
+    /// It is described by `original_format`, so data that was converted before upload is exported
+    /// in the format it was given in, rather than as 16-bit PCM. Returns `Error::InvalidValue` if
+    /// the length of `data` doesn't match the buffer's length in that format.
+    pub fn to_wav<'d>(&self, data: &'d [u8]) -> Result<Wav<'d>, Error> {
+        let format = self.original_format;
+        let size = self.frames / format.sample_type.block_frames() * format.block_size();
+        if data.len() != size {
+            return Err(Error::InvalidValue);
+        }
+        Ok(Wav::new(format, self.frequency, data))
+    }
+}
+
+#[derive(Debug)]
+/// A block of audio data that can be played by a `Source`.
+pub struct Buffer<'a> {
+    context: &'a Context<'a>,
+    name: ALuint,
+    format: Option<Format>,
+    original_format: Option<Format>,
+    frames: usize,
+    storage: MapFlags,
+    mapped: Cell<bool>,
+    ambisonic_order: u8,
+    block_alignment: u16,
+    #[cfg(feature = "std")]
+    callback: Option<Box<Callback>>,
+}
+
+impl<'a> Buffer<'a> {
+    /// Creates a new, empty buffer.
+    pub fn new(context: &'a Context<'a>) -> Result<Self, Error> {
+        let mut name = 0;
+        context
+            .call(|| unsafe { alGenBuffers(1, &mut name) })
+            .map(|()| Self {
+                context,
+                name,
+                format: None,
+                original_format: None,
+                frames: 0,
+                storage: MapFlags::default(),
+                mapped: Cell::new(false),
+                ambisonic_order: 1,
+                block_alignment: 0,
+                #[cfg(feature = "std")]
+                callback: None,
+            })
+    }
+
+    /// Creates a new buffer containing interleaved PCM samples.
+    ///
+    /// See `set_data` for details.
+    pub fn from_samples<T: Sample>(
+        context: &'a Context<'a>,
+        layout: ChannelLayout,
+        samples: &[T],
+        frequency: u32,
+    ) -> Result<Self, Error> {
+        let mut buffer = Self::new(context)?;
+        buffer.set_data(layout, samples, frequency).map(|()| buffer)
+    }
+
+    /// Creates a new buffer containing the audio data from a WAV file held in memory.
+    pub fn from_wav(context: &'a Context<'a>, bytes: &[u8]) -> Result<Self, Error> {
+        let wav = Wav::parse(bytes)?;
+        let mut buffer = Self::new(context)?;
+        buffer
+            .set_raw_data(wav.format(), wav.data(), wav.frequency())
+            .map(|()| buffer)
+    }
+
+    #[cfg(feature = "std")]
+    /// Creates a new buffer containing the audio data from a WAV file on disk.
+    pub fn from_wav_file<P: AsRef<Path>>(context: &'a Context<'a>, path: P) -> Result<Self, Error> {
+        fs::read(path)
+            .map_err(Error::Io)
+            .and_then(|bytes| Self::from_wav(context, &bytes))
+    }
+
+    #[cfg(feature = "std")]
+    /// Creates a new buffer containing all of the audio data produced by a decoder.
+    pub fn from_decoder<D: Decoder>(
+        context: &'a Context<'a>,
+        mut decoder: D,
+    ) -> Result<Self, Error> {
+        const CHUNK_SIZE: usize = 0x1_0000;
+        let mut data = Vec::new();
+        loop {
+            let len = data.len();
+            data.resize(len + CHUNK_SIZE, 0);
+            let n = decoder.read(&mut data[len..])?;
+            data.truncate(len + n);
+            if n == 0 {
+                break;
+            }
+        }
+        let mut buffer = Self::new(context)?;
+        buffer
+            .set_raw_data(decoder.format(), &data, decoder.frequency())
+            .map(|()| buffer)
+    }
+
+    /// Replaces the contents of this buffer with interleaved PCM samples.
+    ///
+    /// The number of samples must be a multiple of the number of channels in `layout`.
+    ///
+    /// With the `std` feature enabled, floating-point samples are converted to 16-bit PCM if the
+    /// context can't play them directly. `BufferInfo::original_format` still describes them as
+    /// uploaded.
+    pub fn set_data<T: Sample>(
+        &mut self,
+        layout: ChannelLayout,
+        samples: &[T],
+        frequency: u32,
+    ) -> Result<(), Error> {
+        let format = Format::new(layout, T::TYPE);
+        #[cfg(feature = "std")]
+        {
+            if T::TYPE.extension().is_some() && !self.is_supported(format) {
+                return self
+                    .set_data(layout, &format::to_i16(samples), frequency)
+                    .map(|()| self.original_format = Some(format));
+            }
+        }
+        self.set_raw_data(format, util::as_bytes(samples), frequency)
+    }
+
+    /// Replaces the contents of this buffer with audio data in the given format.
+    ///
+    /// The length of `data` must be a multiple of the format's block size.
+    ///
+    /// With the `std` feature enabled, mu-law and A-law data is decoded to 16-bit PCM if the
+    /// context can't play it directly. `BufferInfo::original_format` still describes it as
+    /// uploaded.
+    pub fn set_raw_data(
+        &mut self,
+        format: Format,
+        data: &[u8],
+        frequency: u32,
+    ) -> Result<(), Error> {
+        if !data.len().is_multiple_of(format.block_size()) {
+            return Err(Error::InvalidValue);
+        }
+        #[cfg(feature = "std")]
+        {
+            let decode: Option<G711Decoder> = match format.sample_type {
+                SampleType::MuLaw => Some(convert::decode_mulaw),
+                SampleType::ALaw => Some(convert::decode_alaw),
+                _ => None,
+            };
+            if let Some(decode) = decode {
+                if !self.is_supported(format) {
+                    let mut samples = std::vec![0; data.len()];
+                    decode(data, &mut samples);
+                    return self
+                        .set_data(format.layout, &samples, frequency)
+                        .map(|()| self.original_format = Some(format));
+                }
+            }
+        }
+        let al_format = self.prepare_upload(format)?;
+        let size = ALsizei::try_from(data.len()).map_err(|_| Error::InvalidValue)?;
+        let frequency = ALsizei::try_from(frequency).map_err(|_| Error::InvalidValue)?;
+        let name = self.name;
+        self.context.call(|| unsafe {
+            alBufferData(name, al_format, data.as_ptr().cast(), size, frequency)
+        })?;
+        let frames = data.len() / format.block_size() * format.sample_type.block_frames();
+        self.finish_upload(format, frames, MapFlags::default());
+        Ok(())
+    }
+
+    /// Replaces the contents of this buffer with `frames` frames of zeroed storage that can be
+    /// mapped into memory with `map_mut`.
+    ///
+    /// `frames` must be a multiple of the format's block length. Requires `AL_SOFT_map_buffer`.
+    pub fn set_storage(
+        &mut self,
+        format: Format,
+        frames: usize,
+        frequency: u32,
+        flags: MapFlags,
+    ) -> Result<(), Error> {
+        let buffer_storage: BufferStorageFn = unsafe {
+            self.context
+                .extension_function("AL_SOFT_map_buffer", "alBufferStorageSOFT")?
+        };
+        let block_frames = format.sample_type.block_frames();
+        if !frames.is_multiple_of(block_frames) {
+            return Err(Error::InvalidValue);
+        }
+        let al_format = self.prepare_upload(format)?;
+        let size = ALsizei::try_from(frames / block_frames * format.block_size())
+            .map_err(|_| Error::InvalidValue)?;
+        let frequency = ALsizei::try_from(frequency).map_err(|_| Error::InvalidValue)?;
+        let name = self.name;
+        self.context.call(|| unsafe {
+            buffer_storage(name, al_format, ptr::null(), size, frequency, flags.to_al())
+        })?;
+        self.finish_upload(format, frames, flags);
+        Ok(())
+    }
+
+    /// Overwrites part of this buffer's audio data with interleaved PCM samples, starting at the
+    /// given frame.
+    ///
+    /// The samples must be of the same type and layout as the buffer's existing data, and must
+    /// not extend past its end. This can be used while the buffer is playing, such as to stream
+    /// into a looping buffer. Requires `AL_SOFT_buffer_sub_data`.
+    pub fn write_at<T: Sample>(&self, frame: usize, samples: &[T]) -> Result<(), Error> {
+        let buffer_sub_data: BufferSubDataFn = unsafe {
+            self.context
+                .extension_function("AL_SOFT_buffer_sub_data", "alBufferSubDataSOFT")?
+        };
+        let format = match self.format {
+            Some(format) if format.sample_type == T::TYPE => format,
+            _ => return Err(Error::InvalidValue),
+        };
+        let channels = format.layout.channels();
+        if !samples.len().is_multiple_of(channels)
+            || frame > self.frames
+            || samples.len() / channels > self.frames - frame
+        {
+            return Err(Error::InvalidValue);
+        }
+        let al_format = format.to_al().ok_or(Error::UnsupportedFormat(format))?;
+        let data = util::as_bytes(samples);
+        let offset =
+            ALsizei::try_from(frame * format.block_size()).map_err(|_| Error::InvalidValue)?;
+        let length = ALsizei::try_from(data.len()).map_err(|_| Error::InvalidValue)?;
+        let name = self.name;
+        self.context.call(|| unsafe {
+            buffer_sub_data(name, al_format, data.as_ptr().cast(), offset, length)
+        })
+    }
+
+    /// Maps this buffer's storage into memory as a slice of samples.
+    ///
+    /// The storage must have been created by `set_storage` with a sample type matching `T` and
+    /// with both `read` and `write` set, and is mapped with the same flags it was created with.
+    /// Returns `Error::InvalidOperation` if either flag is missing, or if the buffer is already
+    /// mapped. Unless the storage is persistent, the buffer can't be played while it is mapped.
+    /// Requires `AL_SOFT_map_buffer`.
+    ///
+    /// # Examples
+    ///
+    /// Writing into persistent storage while the buffer is playing:
+    ///
+    /// ```no_run
+    /// # use alkaline::{Buffer, ChannelLayout, Context, Device, Error, Format, MapFlags, SampleType,
+    /// #     Source};
+    /// # fn main() -> Result<(), Error> {
+    /// let device = Device::open_default()?;
+    /// let context = Context::new(&device)?;
+    /// let mut buffer = Buffer::new(&context)?;
+    /// let format = Format::new(ChannelLayout::Mono, SampleType::I16);
+    /// let flags = MapFlags {
+    ///     read: true,
+    ///     write: true,
+    ///     persistent: true,
+    /// };
+    /// buffer.set_storage(format, 48_000, 48_000, flags)?;
+    /// let mut source = Source::new(&context)?;
+    /// source.set_buffer(Some(&buffer))?;
+    /// source.play()?;
+    ///
+    /// let mut map = buffer.map_mut::<i16>()?;
+    /// for (i, sample) in map[24_000..].iter_mut().enumerate() {
+    ///     *sample = if i % 100 < 50 { 8_192 } else { -8_192 };
+    /// }
+    /// map.flush(24_000..48_000)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn map_mut<T: Sample>(&self) -> Result<BufferMap<'_, T>, Error> {
+        let (map_buffer, unmap_buffer): (MapBufferFn, UnmapBufferFn) = unsafe {
+            (
+                self.context
+                    .extension_function("AL_SOFT_map_buffer", "alMapBufferSOFT")?,
+                self.context
+                    .extension_function("AL_SOFT_map_buffer", "alUnmapBufferSOFT")?,
+            )
+        };
+        let flush: Option<FlushMappedBufferFn> = if self.storage.persistent {
+            Some(unsafe {
+                self.context
+                    .extension_function("AL_SOFT_map_buffer", "alFlushMappedBufferSOFT")?
+            })
+        } else {
+            None
+        };
+        let format = match self.format {
+            Some(format) if format.sample_type == T::TYPE => format,
+            _ => return Err(Error::InvalidValue),
+        };
+        if !self.storage.read || !self.storage.write || self.mapped.get() {
+            return Err(Error::InvalidOperation);
+        }
+        let len = self.frames * format.layout.channels();
+        let length = ALsizei::try_from(self.frames * format.block_size())
+            .map_err(|_| Error::InvalidValue)?;
+        let name = self.name;
+        let access = self.storage.to_al();
+        let data = self
+            .context
+            .call(|| unsafe { map_buffer(name, 0, length, access) })?;
+        if data.is_null() {
+            return Err(Error::InvalidOperation);
+        }
+        self.mapped.set(true);
+        Ok(BufferMap::new(
+            self.context,
+            name,
+            &self.mapped,
+            data.cast(),
+            len,
+            unmap_buffer,
+            flush,
+        ))
+    }
+
+    #[cfg(feature = "std")]
+    /// Makes this buffer read its audio data from `callback` as it is played, instead of storing
+    /// it.
+    ///
+    /// `callback` is called from the mixer thread with a slice to fill, and returns the number of
+    /// bytes it wrote, which must be a multiple of the format's block size. Writing less than the
+    /// whole slice ends playback. The callback should return promptly, as the mixer waits for it.
+    ///
+    /// The callback is released once other data is uploaded to this buffer, or the buffer is
+    /// deleted. Requires `AL_SOFT_callback_buffer`.
+    pub fn set_callback<F: FnMut(&mut [u8]) -> usize + Send + 'static>(
+        &mut self,
+        format: Format,
+        frequency: u32,
+        callback: F,
+    ) -> Result<(), Error> {
+        let set_callback: BufferCallbackFn = unsafe {
+            self.context
+                .extension_function("AL_SOFT_callback_buffer", "alBufferCallbackSOFT")?
+        };
+        let al_format = self.prepare_upload(format)?;
+        let frequency = ALsizei::try_from(frequency).map_err(|_| Error::InvalidValue)?;
+        let mut callback = Box::new(Callback(Box::new(callback)));
+        let userptr: *mut Callback = &mut *callback;
+        let name = self.name;
+        self.context.call(|| unsafe {
+            set_callback(
+                name,
+                al_format,
+                frequency,
+                call_buffer_callback,
+                userptr.cast(),
+            )
+        })?;
+        self.finish_upload(format, 0, MapFlags::default());
+        self.callback = Some(callback);
+        Ok(())
+    }
+
+    /// Returns a description of the audio data in this buffer.
+    pub fn info(&self) -> Result<BufferInfo, Error> {
+        let frequency = self.geti(AL_FREQUENCY)? as u32;
+        let size = self.geti(AL_SIZE)? as usize;
+        let format = match self.format {
+            Some(format) => format,
+            // The buffer is empty, so describe whatever the implementation reports for it.
+            None => {
+                let channels = self.geti(AL_CHANNELS)? as usize;
+                let layout = ChannelLayout::from_channels(channels)
+                    .ok_or(Error::UnsupportedChannels(channels))?;
+                let sample_type = match self.geti(AL_BITS)? {
+                    8 => SampleType::U8,
+                    16 => SampleType::I16,
+                    32 => SampleType::F32,
+                    64 => SampleType::F64,
+                    _ => return Err(Error::InvalidOperation),
+                };
+                Format::new(layout, sample_type)
+            }
+        };
+        Ok(BufferInfo {
+            format,
+            original_format: self.original_format.unwrap_or(format),
+            frequency,
+            size,
+            frames: size / format.block_size() * format.sample_type.block_frames(),
+        })
+    }
+
+    /// Returns the length of the audio data in this buffer, in frames.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Sets the range of frames that a looping source repeats, from `start` up to but not
+    /// including `end`.
+    ///
+    /// The loop points are reset whenever new data is uploaded, and cannot be changed while the
+    /// buffer is attached to a source. Returns `Error::InvalidValue` unless
+    /// `start < end <= self.frames()`.
+    ///
+    /// Requires `AL_SOFT_loop_points`; without it, sources always loop over the whole buffer.
+    pub fn set_loop_points(&mut self, start: usize, end: usize) -> Result<(), Error> {
+        self.context.require_extension("AL_SOFT_loop_points")?;
+        if start >= end || end > self.frames {
+            return Err(Error::InvalidValue);
+        }
+        let start = ALint::try_from(start).map_err(|_| Error::InvalidValue)?;
+        let end = ALint::try_from(end).map_err(|_| Error::InvalidValue)?;
+        let values = [start, end];
+        let name = self.name;
+        self.context
+            .call(|| unsafe { alBufferiv(name, AL_LOOP_POINTS_SOFT, values.as_ptr()) })
+    }
+
+    /// Returns the range of frames that a looping source repeats.
+    ///
+    /// Requires `AL_SOFT_loop_points`.
+    pub fn loop_points(&self) -> Result<(usize, usize), Error> {
+        self.context.require_extension("AL_SOFT_loop_points")?;
+        let mut values: [ALint; 2] = [0; 2];
+        let name = self.name;
+        self.context
+            .call(|| unsafe { alGetBufferiv(name, AL_LOOP_POINTS_SOFT, values.as_mut_ptr()) })
+            .map(|()| (values[0] as usize, values[1] as usize))
+    }
+
+    /// Sets the channel ordering used by subsequent B-Format uploads to this buffer.
+    ///
+    /// Requires `AL_SOFT_bformat_ex`.
+    pub fn set_ambisonic_layout(&mut self, layout: AmbisonicLayout) -> Result<(), Error> {
+        self.context.require_extension("AL_SOFT_bformat_ex")?;
+        self.seti(AL_AMBISONIC_LAYOUT_SOFT, layout.to_al())
+    }
+
+    /// Sets the channel normalization used by subsequent B-Format uploads to this buffer.
+    ///
+    /// Requires `AL_SOFT_bformat_ex`.
+    pub fn set_ambisonic_scaling(&mut self, scaling: AmbisonicScaling) -> Result<(), Error> {
+        self.context.require_extension("AL_SOFT_bformat_ex")?;
+        self.seti(AL_AMBISONIC_SCALING_SOFT, scaling.to_al())
+    }
+
+    pub(crate) fn name(&self) -> ALuint {
+        self.name
+    }
+
+    /// Checks that the context can play a format and sets the unpack parameters it needs,
+    /// returning its AL enumeration value.
+    fn prepare_upload(&mut self, format: Format) -> Result<ALenum, Error> {
+        let al_format = format.to_al().ok_or(Error::UnsupportedFormat(format))?;
+        for extension in format
+            .extensions()
+            .iter()
+            .filter_map(|&extension| extension)
+        {
+            self.context.require_extension(extension)?;
+        }
+        // Unpack parameters persist between uploads, so only change them when they differ.
+        if let Some(order) = format.layout.ambisonic_order() {
+            if order != self.ambisonic_order {
+                self.seti(AL_UNPACK_AMBISONIC_ORDER_SOFT, ALint::from(order))?;
+                self.ambisonic_order = order;
+            }
+        }
+        let alignment = format.sample_type.unpack_alignment();
+        if alignment != self.block_alignment {
+            self.seti(AL_UNPACK_BLOCK_ALIGNMENT_SOFT, ALint::from(alignment))?;
+            self.block_alignment = alignment;
+        }
+        Ok(al_format)
+    }
+
+    /// Records the new contents of this buffer after a successful upload.
+    fn finish_upload(&mut self, format: Format, frames: usize, storage: MapFlags) {
+        self.format = Some(format);
+        self.original_format = Some(format);
+        self.frames = frames;
+        self.storage = storage;
+        // The implementation no longer refers to any previous callback, so it can be dropped.
+        #[cfg(feature = "std")]
+        {
+            self.callback = None;
+        }
+    }
+
+    #[cfg(feature = "std")]
+    /// Returns whether the context can play audio data in the given format without conversion.
+    fn is_supported(&self, format: Format) -> bool {
+        format.to_al().is_some()
+            && format
+                .extensions()
+                .iter()
+                .filter_map(|&extension| extension)
+                .all(|extension| self.context.is_extension_present(extension))
+    }
+
+    fn geti(&self, param: ALenum) -> Result<ALint, Error> {
+        let name = self.name;
+        let mut value = 0;
+        self.context
+            .call(|| unsafe { alGetBufferi(name, param, &mut value) })
+            .map(|()| value)
+    }
+
+    fn seti(&self, param: ALenum, value: ALint) -> Result<(), Error> {
+        let name = self.name;
+        self.context
+            .call(|| unsafe { alBufferi(name, param, value) })
+    }
+}
+
+impl<'a> Drop for Buffer<'a> {
+    fn drop(&mut self) {
+        let name = self.name;
+        let deleted = self.context.call(|| unsafe { alDeleteBuffers(1, &name) });
+        if deleted.is_err() {
+            // The implementation may still call the callback, so it must never be freed.
+            #[cfg(feature = "std")]
+            mem::forget(self.callback.take());
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+/// The boxed closure behind a `Callback`.
+type BufferCallback = Box<dyn FnMut(&mut [u8]) -> usize + Send>;
+
+#[cfg(feature = "std")]
+/// A closure that supplies audio data to a callback buffer.
+struct Callback(BufferCallback);
+
+#[cfg(feature = "std")]
+impl Debug for Callback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("Callback")
+    }
+}
+
+#[cfg(feature = "std")]
+/// Calls a `Callback` on behalf of the implementation.
+///
+/// A panic can't unwind into the mixer, so it ends playback instead.
+unsafe extern "C" fn call_buffer_callback(
+    userptr: *mut ALvoid,
+    sampledata: *mut ALvoid,
+    numbytes: ALsizei,
+) -> ALsizei {
+    let callback = &mut *userptr.cast::<Callback>();
+    let len = numbytes as usize;
+    let data = slice::from_raw_parts_mut(sampledata.cast::<u8>(), len);
+    match panic::catch_unwind(AssertUnwindSafe(|| (callback.0)(data))) {
+        Ok(written) => written.min(len) as ALsizei,
+        Err(_) => 0,
+    }
+}
@@ -0,0 +1,286 @@
+// Copyright (c) 2018 FaultyRAM
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the
+// MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at
+// your option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use bindgen_openal_sys::{
+    alDisable, alDistanceModel, alDopplerFactor, alDopplerVelocity, alEnable, alGetError,
+    alGetFloat, alGetInteger, alGetProcAddress, alIsEnabled, alIsExtensionPresent, alSpeedOfSound,
+    alcCreateContext, alcDestroyContext, alcGetCurrentContext, alcMakeContextCurrent,
+    alcProcessContext, alcSuspendContext, ALCcontext, ALCint, ALenum, ALfloat, ALC_TRUE,
+    AL_DISTANCE_MODEL, AL_DOPPLER_FACTOR, AL_DOPPLER_VELOCITY, AL_SPEED_OF_SOUND, AL_TRUE,
+};
+use core::convert::TryFrom;
+use core::{mem, ptr};
+use device::Device;
+use distance::DistanceModel;
+use error::Error;
+use ext::{DeferUpdatesFn, ProcessUpdatesFn, ALC_MAX_AUXILIARY_SENDS, AL_SOURCE_DISTANCE_MODEL};
+use util;
+
+#[derive(Debug)]
+/// A set of listener, source and global state attached to a playback device.
+///
+/// OpenAL directs most calls at whichever context is current. Operations on a `Context` or on
+/// objects created from it make that context current first if it isn't already.
+pub struct Context<'a> {
+    ptr: *mut ALCcontext,
+    device: &'a Device,
+    auxiliary_sends: usize,
+}
+
+impl<'a> Context<'a> {
+    /// Creates a new context on the given device.
+    pub fn new(device: &'a Device) -> Result<Self, Error> {
+        Self::with_attributes(device, ptr::null())
+    }
+
+    /// Creates a new context on the given device, requesting the given number of auxiliary
+    /// sends per source.
+    ///
+    /// The implementation may provide fewer sends than requested; `auxiliary_sends` returns the
+    /// number actually available. Requires `ALC_EXT_EFX`.
+    pub fn with_auxiliary_sends(device: &'a Device, sends: usize) -> Result<Self, Error> {
+        if !device.is_extension_present("ALC_EXT_EFX") {
+            return Err(Error::ExtensionNotPresent("ALC_EXT_EFX"));
+        }
+        let sends = ALCint::try_from(sends).map_err(|_| Error::InvalidValue)?;
+        let attributes = [ALC_MAX_AUXILIARY_SENDS, sends, 0];
+        Self::with_attributes(device, attributes.as_ptr())
+    }
+
+    fn with_attributes(device: &'a Device, attributes: *const ALCint) -> Result<Self, Error> {
+        let ptr = unsafe { alcCreateContext(device.as_ptr(), attributes) };
+        if ptr.is_null() {
+            return Err(device.error().unwrap_or(Error::InvalidContext));
+        }
+        let mut context = Self {
+            ptr,
+            device,
+            auxiliary_sends: 0,
+        };
+        if device.is_extension_present("ALC_EXT_EFX") {
+            let sends = device.get_integer(ALC_MAX_AUXILIARY_SENDS)?;
+            context.auxiliary_sends = sends.max(0) as usize;
+        }
+        Ok(context)
+    }
+
+    /// Returns the device this context was created on.
+    pub fn device(&self) -> &'a Device {
+        self.device
+    }
+
+    /// Returns whether this context is the current context.
+    pub fn is_current(&self) -> bool {
+        unsafe { alcGetCurrentContext() == self.ptr }
+    }
+
+    /// Makes this context the current context.
+    pub fn make_current(&self) -> Result<(), Error> {
+        if unsafe { alcMakeContextCurrent(self.ptr) } == ALC_TRUE {
+            Ok(())
+        } else {
+            Err(self.device.error().unwrap_or(Error::InvalidContext))
+        }
+    }
+
+    /// Returns the number of auxiliary sends available to each source, or 0 if EFX is not
+    /// supported.
+    pub fn auxiliary_sends(&self) -> usize {
+        self.auxiliary_sends
+    }
+
+    /// Returns whether the given AL extension is supported by this context.
+    pub fn is_extension_present(&self, name: &str) -> bool {
+        let present = self
+            .call(|| util::with_c_str(name, |name| unsafe { alIsExtensionPresent(name.cast()) }));
+        match present {
+            Ok(Some(present)) => present == AL_TRUE,
+            _ => false,
+        }
+    }
+
+    /// Sets the distance model used by sources that don't override it.
+    pub fn set_distance_model(&self, model: DistanceModel) -> Result<(), Error> {
+        self.call(|| unsafe { alDistanceModel(model.to_al()) })
+    }
+
+    /// Returns the distance model used by sources that don't override it.
+    pub fn distance_model(&self) -> Result<DistanceModel, Error> {
+        self.call(|| unsafe { alGetInteger(AL_DISTANCE_MODEL) })
+            .and_then(DistanceModel::from_al)
+    }
+
+    /// Enables or disables per-source distance models.
+    ///
+    /// This is a context-wide switch. While it is enabled, every source uses its own distance
+    /// model, set with `Source::set_distance_model`, and sources that haven't been given one use
+    /// `DistanceModel::InverseClamped` rather than the context's model. It is disabled by
+    /// default. Requires `AL_EXT_source_distance_model`.
+    pub fn set_source_distance_models(&self, enabled: bool) -> Result<(), Error> {
+        self.require_extension("AL_EXT_source_distance_model")?;
+        if enabled {
+            self.call(|| unsafe { alEnable(AL_SOURCE_DISTANCE_MODEL) })
+        } else {
+            self.call(|| unsafe { alDisable(AL_SOURCE_DISTANCE_MODEL) })
+        }
+    }
+
+    /// Returns whether per-source distance models are enabled.
+    ///
+    /// Requires `AL_EXT_source_distance_model`.
+    pub fn source_distance_models(&self) -> Result<bool, Error> {
+        self.require_extension("AL_EXT_source_distance_model")?;
+        self.call(|| unsafe { alIsEnabled(AL_SOURCE_DISTANCE_MODEL) })
+            .map(|enabled| enabled == AL_TRUE)
+    }
+
+    /// Sets the scale applied to the Doppler effect. The default is 1; 0 disables it.
+    pub fn set_doppler_factor(&self, factor: f32) -> Result<(), Error> {
+        self.call(|| unsafe { alDopplerFactor(factor) })
+    }
+
+    /// Returns the scale applied to the Doppler effect.
+    pub fn doppler_factor(&self) -> Result<f32, Error> {
+        self.getf(AL_DOPPLER_FACTOR)
+    }
+
+    /// Sets the speed of sound used in Doppler calculations, in world units per second.
+    ///
+    /// The default is 343.3, which is correct for world units of one metre.
+    pub fn set_speed_of_sound(&self, speed: f32) -> Result<(), Error> {
+        self.call(|| unsafe { alSpeedOfSound(speed) })
+    }
+
+    /// Returns the speed of sound used in Doppler calculations, in world units per second.
+    pub fn speed_of_sound(&self) -> Result<f32, Error> {
+        self.getf(AL_SPEED_OF_SOUND)
+    }
+
+    /// Sets the legacy Doppler velocity, which scales the speed of sound.
+    ///
+    /// This exists for compatibility with OpenAL 1.0; prefer `set_speed_of_sound`.
+    pub fn set_doppler_velocity(&self, velocity: f32) -> Result<(), Error> {
+        self.call(|| unsafe { alDopplerVelocity(velocity) })
+    }
+
+    /// Returns the legacy Doppler velocity.
+    pub fn doppler_velocity(&self) -> Result<f32, Error> {
+        self.getf(AL_DOPPLER_VELOCITY)
+    }
+
+    /// Calls `f`, deferring the effects of any changes it makes until it returns.
+    ///
+    /// This applies a group of updates, such as moving many sources, in a single mixing update.
+    /// `AL_SOFT_deferred_updates` is used when supported; otherwise the context is suspended
+    /// instead, which not all implementations honour. Updates resume even if `f` panics.
+    pub fn batch<T, F: FnOnce(&Self) -> T>(&self, f: F) -> Result<T, Error> {
+        let functions = unsafe {
+            self.extension_function::<DeferUpdatesFn>(
+                "AL_SOFT_deferred_updates",
+                "alDeferUpdatesSOFT",
+            )
+            .and_then(|defer| {
+                self.extension_function::<ProcessUpdatesFn>(
+                    "AL_SOFT_deferred_updates",
+                    "alProcessUpdatesSOFT",
+                )
+                .map(|process| (defer, process))
+            })
+        };
+        let process = match functions {
+            Ok((defer, process)) => {
+                self.call(|| unsafe { defer() })?;
+                Some(process)
+            }
+            Err(Error::ExtensionNotPresent(_)) => {
+                self.call(|| unsafe { alcSuspendContext(self.ptr) })?;
+                None
+            }
+            Err(e) => return Err(e),
+        };
+        let _resume = Resume {
+            context: self,
+            process,
+        };
+        Ok(f(self))
+    }
+
+    /// Returns an error if the given AL or ALC extension is not supported by this context.
+    pub(crate) fn require_extension(&self, name: &'static str) -> Result<(), Error> {
+        let present = if name.starts_with("ALC_") {
+            self.device.is_extension_present(name)
+        } else {
+            self.is_extension_present(name)
+        };
+        if present {
+            Ok(())
+        } else {
+            Err(Error::ExtensionNotPresent(name))
+        }
+    }
+
+    /// Looks up an AL extension function, returning an error if the extension is not supported.
+    ///
+    /// `F` must be the function pointer type of the named function.
+    pub(crate) unsafe fn extension_function<F: Copy>(
+        &self,
+        extension: &'static str,
+        function: &str,
+    ) -> Result<F, Error> {
+        self.require_extension(extension)?;
+        let address =
+            self.call(|| util::with_c_str(function, |function| alGetProcAddress(function.cast())))?;
+        match address {
+            Some(address) if !address.is_null() => Ok(mem::transmute_copy(&address)),
+            _ => Err(Error::ExtensionNotPresent(extension)),
+        }
+    }
+
+    fn getf(&self, param: ALenum) -> Result<ALfloat, Error> {
+        self.call(|| unsafe { alGetFloat(param) })
+    }
+
+    /// Makes this context current, then calls `f` and checks for an AL error.
+    pub(crate) fn call<T, F: FnOnce() -> T>(&self, f: F) -> Result<T, Error> {
+        if !self.is_current() {
+            self.make_current()?;
+        }
+        unsafe {
+            let _ = alGetError();
+            let result = f();
+            Error::from_al(alGetError()).map_or(Ok(result), Err)
+        }
+    }
+}
+
+impl<'a> Drop for Context<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            if self.is_current() {
+                let _ = alcMakeContextCurrent(ptr::null_mut());
+            }
+            alcDestroyContext(self.ptr);
+        }
+    }
+}
+
+/// Resumes updates on a context when dropped, ending a `Context::batch`.
+struct Resume<'c, 'a> {
+    context: &'c Context<'a>,
+    process: Option<ProcessUpdatesFn>,
+}
+
+impl<'c, 'a> Drop for Resume<'c, 'a> {
+    fn drop(&mut self) {
+        let ptr = self.context.ptr;
+        let _ = match self.process {
+            Some(process) => self.context.call(|| unsafe { process() }),
+            None => self.context.call(|| unsafe { alcProcessContext(ptr) }),
+        };
+    }
+}
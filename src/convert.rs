@@ -0,0 +1,584 @@
+// Copyright (c) 2018 FaultyRAM
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the
+// MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at
+// your option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Sample format and channel layout conversion.
+//!
+//! Core OpenAL only accepts 8-bit unsigned and 16-bit signed PCM in mono or stereo. These routines
+//! convert audio data from other sample types and layouts into something it can play.
+
+use core::f32::consts::FRAC_1_SQRT_2;
+use core::slice;
+use format::ChannelLayout;
+
+/// A seed used by `Dither::default`.
+const DEFAULT_SEED: u32 = 0x9E37_79B9;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[repr(transparent)]
+/// A packed, little-endian, signed 24-bit sample.
+pub struct I24(pub [u8; 3]);
+
+impl I24 {
+    /// Creates a 24-bit sample from the low 24 bits of `value`.
+    pub fn new(value: i32) -> Self {
+        I24([value as u8, (value >> 8) as u8, (value >> 16) as u8])
+    }
+
+    /// Returns the value of this sample, sign-extended to 32 bits.
+    pub fn get(self) -> i32 {
+        let [lo, mid, hi] = self.0;
+        (i32::from(lo) << 8 | i32::from(mid) << 16 | i32::from(hi) << 24) >> 8
+    }
+
+    /// Reinterprets a slice of packed 24-bit sample data as a slice of samples.
+    ///
+    /// Any trailing bytes that do not form a whole sample are ignored.
+    pub fn from_bytes(bytes: &[u8]) -> &[Self] {
+        unsafe { slice::from_raw_parts(bytes.as_ptr().cast(), bytes.len() / 3) }
+    }
+}
+
+#[derive(Clone, Debug)]
+/// A source of noise used to dither samples when reducing their resolution.
+pub struct Dither {
+    state: u32,
+    amplitude: f32,
+}
+
+impl Dither {
+    /// Creates a triangular probability density function (TPDF) dither source with a peak
+    /// amplitude of one least significant bit.
+    pub fn triangular(seed: u32) -> Self {
+        Self {
+            state: if seed == 0 { DEFAULT_SEED } else { seed },
+            amplitude: 1.0,
+        }
+    }
+
+    /// Creates a dither source that adds no noise, so that samples are simply rounded.
+    pub fn none() -> Self {
+        Self {
+            state: DEFAULT_SEED,
+            amplitude: 0.0,
+        }
+    }
+
+    /// Returns the next noise value, in least significant bits.
+    pub(crate) fn next(&mut self) -> f32 {
+        if self.amplitude == 0.0 {
+            0.0
+        } else {
+            (self.uniform() - self.uniform()) * self.amplitude
+        }
+    }
+
+    /// Returns a uniformly distributed value in `[0, 1)`.
+    fn uniform(&mut self) -> f32 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        (self.state >> 8) as f32 / (1 << 24) as f32
+    }
+}
+
+impl Default for Dither {
+    fn default() -> Self {
+        Self::triangular(DEFAULT_SEED)
+    }
+}
+
+/// A high-resolution sample type that can be converted to a lower resolution.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait InputSample: Copy + private::Sealed {
+    /// Returns this sample scaled to the range `[-1.0, 1.0]`.
+    fn to_f32(self) -> f32;
+}
+
+impl InputSample for f32 {
+    fn to_f32(self) -> f32 {
+        self
+    }
+}
+
+impl InputSample for f64 {
+    fn to_f32(self) -> f32 {
+        self as f32
+    }
+}
+
+impl InputSample for i32 {
+    fn to_f32(self) -> f32 {
+        self as f32 / 2_147_483_648.0
+    }
+}
+
+impl InputSample for I24 {
+    fn to_f32(self) -> f32 {
+        self.get() as f32 / 8_388_608.0
+    }
+}
+
+/// A sample type that OpenAL accepts without extensions.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait OutputSample: Copy + private::Sealed {
+    /// Quantizes a sample in the range `[-1.0, 1.0]`, adding `noise` least significant bits first.
+    fn quantize(value: f32, noise: f32) -> Self;
+}
+
+impl OutputSample for i16 {
+    fn quantize(value: f32, noise: f32) -> Self {
+        round(value * 32_768.0 + noise).clamp(-32_768, 32_767) as i16
+    }
+}
+
+impl OutputSample for u8 {
+    fn quantize(value: f32, noise: f32) -> Self {
+        (round(value * 128.0 + noise) + 128).clamp(0, 255) as u8
+    }
+}
+
+/// Converts samples to a lower resolution, dithering them with noise from `dither`.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+pub fn convert<I: InputSample, O: OutputSample>(src: &[I], dst: &mut [O], dither: &mut Dither) {
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination lengths differ"
+    );
+    for (out, &sample) in dst.iter_mut().zip(src) {
+        *out = O::quantize(sample.to_f32(), dither.next());
+    }
+}
+
+/// Decodes G.711 mu-law samples to 16-bit PCM.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+pub fn decode_mulaw(src: &[u8], dst: &mut [i16]) {
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination lengths differ"
+    );
+    for (out, &sample) in dst.iter_mut().zip(src) {
+        let sample = !sample;
+        let magnitude = ((i16::from(sample & 0x0F) << 3) + 0x84) << ((sample & 0x70) >> 4);
+        *out = if sample & 0x80 == 0 {
+            magnitude - 0x84
+        } else {
+            0x84 - magnitude
+        };
+    }
+}
+
+/// Decodes G.711 A-law samples to 16-bit PCM.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` have different lengths.
+pub fn decode_alaw(src: &[u8], dst: &mut [i16]) {
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination lengths differ"
+    );
+    for (out, &sample) in dst.iter_mut().zip(src) {
+        let sample = sample ^ 0x55;
+        let mantissa = i16::from(sample & 0x0F) << 4;
+        let magnitude = match (sample & 0x70) >> 4 {
+            0 => mantissa + 8,
+            segment => (mantissa + 0x108) << (segment - 1),
+        };
+        *out = if sample & 0x80 == 0 {
+            -magnitude
+        } else {
+            magnitude
+        };
+    }
+}
+
+/// Interleaves separate channels of samples into a single buffer of frames.
+///
+/// # Panics
+///
+/// Panics if the channels have different lengths, or if `dst` is not exactly large enough to hold
+/// every sample.
+pub fn interleave<T: Copy>(channels: &[&[T]], dst: &mut [T]) {
+    let frames = channels.first().map_or(0, |channel| channel.len());
+    assert!(
+        channels.iter().all(|channel| channel.len() == frames),
+        "channel lengths differ"
+    );
+    assert_eq!(
+        frames * channels.len(),
+        dst.len(),
+        "destination length is incorrect"
+    );
+    for (i, frame) in dst.chunks_mut(channels.len()).enumerate() {
+        for (out, channel) in frame.iter_mut().zip(channels) {
+            *out = channel[i];
+        }
+    }
+}
+
+/// Splits a buffer of interleaved frames into separate channels.
+///
+/// # Panics
+///
+/// Panics if the channels have different lengths, or if `src` does not contain exactly enough
+/// samples to fill every channel.
+pub fn deinterleave<T: Copy>(src: &[T], channels: &mut [&mut [T]]) {
+    let frames = channels.first().map_or(0, |channel| channel.len());
+    assert!(
+        channels.iter().all(|channel| channel.len() == frames),
+        "channel lengths differ"
+    );
+    assert_eq!(
+        frames * channels.len(),
+        src.len(),
+        "source length is incorrect"
+    );
+    for (i, frame) in src.chunks(channels.len()).enumerate() {
+        for (&sample, channel) in frame.iter().zip(channels.iter_mut()) {
+            channel[i] = sample;
+        }
+    }
+}
+
+/// Mixes interleaved frames from one channel layout into another.
+///
+/// Downmixing to mono or to a pair of speakers folds center and surround channels into left and
+/// right using the usual -3 dB coefficients, and discards the LFE channel. Otherwise each channel
+/// is copied to the matching speaker position, and channels without one are folded into their
+/// neighbours: a center channel into the front pair at -3 dB, side channels into the rear pair
+/// and vice versa, and a rear center channel into the rear pair, or the side pair if there is no
+/// rear pair, at -3 dB. Mixing a layout into itself copies the samples unchanged.
+///
+/// # Panics
+///
+/// Panics if `src` and `dst` do not contain the same number of whole frames, or if either layout is
+/// B-Format.
+pub fn remix(src: &[f32], from: ChannelLayout, dst: &mut [f32], to: ChannelLayout) {
+    let (from_speakers, to_speakers) = (speakers(from), speakers(to));
+    assert_eq!(
+        src.len() % from_speakers.len(),
+        0,
+        "source does not contain whole frames"
+    );
+    assert_eq!(
+        src.len() / from_speakers.len() * to_speakers.len(),
+        dst.len(),
+        "destination length is incorrect"
+    );
+    if from == to {
+        dst.copy_from_slice(src);
+        return;
+    }
+    let src_frames = src.chunks(from_speakers.len());
+    for (in_frame, out_frame) in src_frames.zip(dst.chunks_mut(to_speakers.len())) {
+        match to {
+            ChannelLayout::Mono | ChannelLayout::Stereo | ChannelLayout::Rear => {
+                let (mut left, mut right) = (0.0, 0.0);
+                for (&sample, &speaker) in in_frame.iter().zip(from_speakers) {
+                    let (l, r) = speaker.stereo_gains();
+                    left += sample * l;
+                    right += sample * r;
+                }
+                if to == ChannelLayout::Mono {
+                    out_frame[0] = (left + right) * 0.5;
+                } else {
+                    out_frame[0] = left;
+                    out_frame[1] = right;
+                }
+            }
+            _ => {
+                for (out, &target) in out_frame.iter_mut().zip(to_speakers) {
+                    *out = 0.0;
+                    for (&sample, &speaker) in in_frame.iter().zip(from_speakers) {
+                        *out += sample * speaker.fold_gain(target, to_speakers);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A gain of -3 dB.
+const MINUS_3DB: f32 = FRAC_1_SQRT_2;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// A speaker position within a channel layout.
+enum Speaker {
+    FrontLeft,
+    FrontRight,
+    FrontCenter,
+    Lfe,
+    RearLeft,
+    RearRight,
+    RearCenter,
+    SideLeft,
+    SideRight,
+}
+
+impl Speaker {
+    /// Returns the gain applied to this speaker when mixing it into `target`, one of the speakers
+    /// in `targets`.
+    fn fold_gain(self, target: Speaker, targets: &[Speaker]) -> f32 {
+        use self::Speaker::*;
+
+        if self == target {
+            return 1.0;
+        }
+        if targets.contains(&self) {
+            return 0.0;
+        }
+        match (self, target) {
+            (FrontCenter, FrontLeft) | (FrontCenter, FrontRight) => MINUS_3DB,
+            (SideLeft, RearLeft) | (SideRight, RearRight) => 1.0,
+            (RearLeft, SideLeft) | (RearRight, SideRight) => 1.0,
+            (RearCenter, RearLeft) | (RearCenter, RearRight) => MINUS_3DB,
+            (RearCenter, SideLeft) | (RearCenter, SideRight) if !targets.contains(&RearLeft) => {
+                MINUS_3DB
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the gains applied to this speaker when folding it into a stereo pair.
+    fn stereo_gains(self) -> (f32, f32) {
+        match self {
+            Speaker::FrontLeft => (1.0, 0.0),
+            Speaker::FrontRight => (0.0, 1.0),
+            Speaker::FrontCenter => (MINUS_3DB, MINUS_3DB),
+            Speaker::Lfe => (0.0, 0.0),
+            Speaker::RearLeft | Speaker::SideLeft => (MINUS_3DB, 0.0),
+            Speaker::RearRight | Speaker::SideRight => (0.0, MINUS_3DB),
+            Speaker::RearCenter => (0.5, 0.5),
+        }
+    }
+}
+
+/// Returns the speaker positions of each channel in a layout, in order.
+fn speakers(layout: ChannelLayout) -> &'static [Speaker] {
+    use self::Speaker::*;
+
+    match layout {
+        ChannelLayout::Mono => &[FrontCenter],
+        ChannelLayout::Stereo => &[FrontLeft, FrontRight],
+        ChannelLayout::Rear => &[RearLeft, RearRight],
+        ChannelLayout::Quad => &[FrontLeft, FrontRight, RearLeft, RearRight],
+        ChannelLayout::Surround51 => {
+            &[FrontLeft, FrontRight, FrontCenter, Lfe, SideLeft, SideRight]
+        }
+        ChannelLayout::Surround61 => &[
+            FrontLeft,
+            FrontRight,
+            FrontCenter,
+            Lfe,
+            RearCenter,
+            SideLeft,
+            SideRight,
+        ],
+        ChannelLayout::Surround71 => &[
+            FrontLeft,
+            FrontRight,
+            FrontCenter,
+            Lfe,
+            RearLeft,
+            RearRight,
+            SideLeft,
+            SideRight,
+        ],
+        ChannelLayout::BFormat2D(_) | ChannelLayout::BFormat3D(_) => {
+            panic!("B-Format audio cannot be remixed")
+        }
+    }
+}
+
+/// Rounds to the nearest integer, with halfway cases rounded away from zero.
+fn round(value: f32) -> i32 {
+    let truncated = value as i32;
+    let fraction = value - truncated as f32;
+    if fraction >= 0.5 {
+        truncated + 1
+    } else if fraction <= -0.5 {
+        truncated - 1
+    } else {
+        truncated
+    }
+}
+
+mod private {
+    use super::I24;
+
+    pub trait Sealed {}
+
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+    impl Sealed for i32 {}
+    impl Sealed for I24 {}
+    impl Sealed for i16 {}
+    impl Sealed for u8 {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f32], expected: &[f32]) {
+        assert_eq!(actual.len(), expected.len());
+        for (&a, &e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-6, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn convert_quantizes_and_clamps() {
+        let mut dst = [0i16; 5];
+        convert(
+            &[1.0f32, -1.0, 0.0, 0.5, 2.0],
+            &mut dst,
+            &mut Dither::none(),
+        );
+        assert_eq!(dst, [32767, -32768, 0, 16384, 32767]);
+
+        let mut dst = [0u8; 3];
+        convert(
+            &[I24::new(-8_388_608), I24::new(0), I24::new(8_388_607)],
+            &mut dst,
+            &mut Dither::none(),
+        );
+        assert_eq!(dst, [0, 128, 255]);
+    }
+
+    #[test]
+    fn convert_dithers_within_one_step() {
+        let src = [0.25f32; 64];
+        let mut dst = [0i16; 64];
+        convert(&src, &mut dst, &mut Dither::triangular(1));
+        assert!(dst.iter().all(|&sample| (8191..=8193).contains(&sample)));
+    }
+
+    #[test]
+    fn decode_g711() {
+        let mut dst = [0i16; 4];
+        decode_mulaw(&[0x00, 0xFF, 0x80, 0x7F], &mut dst);
+        assert_eq!(dst, [-32124, 0, 32124, 0]);
+        decode_alaw(&[0xD5, 0x55, 0x2A, 0xAA], &mut dst);
+        assert_eq!(dst, [8, -8, -32256, 32256]);
+    }
+
+    #[test]
+    fn interleave_round_trip() {
+        let mut interleaved = [0; 6];
+        interleave(&[&[1, 2, 3], &[4, 5, 6]], &mut interleaved);
+        assert_eq!(interleaved, [1, 4, 2, 5, 3, 6]);
+        let (mut left, mut right) = ([0; 3], [0; 3]);
+        deinterleave(&interleaved, &mut [&mut left, &mut right]);
+        assert_eq!((left, right), ([1, 2, 3], [4, 5, 6]));
+    }
+
+    #[test]
+    fn remix_same_layout_copies() {
+        let mut dst = [0.0; 2];
+        remix(
+            &[0.5, -0.25],
+            ChannelLayout::Mono,
+            &mut dst,
+            ChannelLayout::Mono,
+        );
+        assert_eq!(dst, [0.5, -0.25]);
+    }
+
+    #[test]
+    fn remix_mono_to_stereo() {
+        let mut dst = [0.0; 2];
+        remix(&[1.0], ChannelLayout::Mono, &mut dst, ChannelLayout::Stereo);
+        assert_close(&dst, &[FRAC_1_SQRT_2, FRAC_1_SQRT_2]);
+    }
+
+    #[test]
+    fn remix_surround51_to_stereo() {
+        let mut dst = [0.0; 2];
+        remix(
+            &[1.0, 1.0, 1.0, 0.5, 0.0, 0.0],
+            ChannelLayout::Surround51,
+            &mut dst,
+            ChannelLayout::Stereo,
+        );
+        assert_close(&dst, &[1.0 + FRAC_1_SQRT_2, 1.0 + FRAC_1_SQRT_2]);
+    }
+
+    #[test]
+    fn remix_folds_sides_into_rears() {
+        let mut dst = [0.0; 4];
+        remix(
+            &[0.1, 0.2, 1.0, 0.5, 0.25, 0.75],
+            ChannelLayout::Surround51,
+            &mut dst,
+            ChannelLayout::Quad,
+        );
+        assert_close(
+            &dst,
+            &[0.1 + FRAC_1_SQRT_2, 0.2 + FRAC_1_SQRT_2, 0.25, 0.75],
+        );
+    }
+
+    #[test]
+    fn remix_folds_rears_into_sides() {
+        let mut dst = [0.0; 6];
+        remix(
+            &[0.1, 0.2, 0.3, 0.4, 1.0, 0.5, 0.25, 0.0],
+            ChannelLayout::Surround71,
+            &mut dst,
+            ChannelLayout::Surround51,
+        );
+        assert_close(&dst, &[0.1, 0.2, 0.3, 0.4, 1.25, 0.5]);
+    }
+
+    #[test]
+    fn remix_folds_rear_center() {
+        let src = [0.0, 0.0, 0.0, 0.0, 1.0, 0.25, 0.5];
+        let mut dst = [0.0; 6];
+        remix(
+            &src,
+            ChannelLayout::Surround61,
+            &mut dst,
+            ChannelLayout::Surround51,
+        );
+        assert_close(
+            &dst,
+            &[
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                0.25 + FRAC_1_SQRT_2,
+                0.5 + FRAC_1_SQRT_2,
+            ],
+        );
+
+        let mut dst = [0.0; 8];
+        remix(
+            &src,
+            ChannelLayout::Surround61,
+            &mut dst,
+            ChannelLayout::Surround71,
+        );
+        assert_close(
+            &dst,
+            &[0.0, 0.0, 0.0, 0.0, FRAC_1_SQRT_2, FRAC_1_SQRT_2, 0.25, 0.5],
+        );
+    }
+}
@@ -6,8 +6,147 @@
 // your option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use bindgen_openal_sys::ALCdevice;
+use bindgen_openal_sys::{
+    alcCloseDevice, alcGetError, alcGetIntegerv, alcGetProcAddress, alcIsExtensionPresent,
+    alcOpenDevice, ALCdevice, ALCenum, ALCint, ALCsizei, ALC_TRUE,
+};
+use core::convert::TryFrom;
+use core::time::Duration;
+use core::{mem, ptr};
+use error::Error;
+use ext::{
+    ALCint64SOFT, GetInteger64vFn, ALC_DEVICE_CLOCK_LATENCY_SOFT, ALC_DEVICE_CLOCK_SOFT,
+    ALC_DEVICE_LATENCY_SOFT,
+};
+use util;
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+/// A reading of a device's mixer clock, paired with the output latency at that moment.
+pub struct DeviceTime {
+    /// The time the device has spent mixing audio since it was opened.
+    pub clock: Duration,
+    /// The time between the mixer producing audio and the audio reaching the output.
+    pub latency: Duration,
+}
 
 #[derive(Debug)]
 /// An audio device that supports either capture (recording) or playback.
 pub struct Device(*mut ALCdevice);
+
+impl Device {
+    /// Opens the default playback device.
+    pub fn open_default() -> Result<Self, Error> {
+        Self::from_ptr(unsafe { alcOpenDevice(ptr::null()) })
+    }
+
+    /// Opens the playback device with the given name.
+    pub fn open(name: &str) -> Result<Self, Error> {
+        util::with_c_str(name, |name| unsafe { alcOpenDevice(name.cast()) })
+            .ok_or(Error::InvalidValue)
+            .and_then(Self::from_ptr)
+    }
+
+    /// Returns whether the given ALC extension is supported by this device.
+    pub fn is_extension_present(&self, name: &str) -> bool {
+        util::with_c_str(name, |name| unsafe {
+            alcIsExtensionPresent(self.0, name.cast())
+        }) == Some(ALC_TRUE)
+    }
+
+    /// Returns the time this device has spent mixing audio since it was opened.
+    ///
+    /// Unlike the wall clock, this only advances as audio is mixed, so it can be used to schedule
+    /// events in step with playback. Requires `ALC_SOFT_device_clock`.
+    pub fn clock(&self) -> Result<Duration, Error> {
+        let mut values = [0];
+        self.get_integer64(ALC_DEVICE_CLOCK_SOFT, &mut values)
+            .map(|()| duration_from_nanos(values[0]))
+    }
+
+    /// Returns the time between the mixer producing audio and the audio reaching the output.
+    ///
+    /// Requires `ALC_SOFT_device_clock`.
+    pub fn latency(&self) -> Result<Duration, Error> {
+        let mut values = [0];
+        self.get_integer64(ALC_DEVICE_LATENCY_SOFT, &mut values)
+            .map(|()| duration_from_nanos(values[0]))
+    }
+
+    /// Returns the mixer clock together with the output latency, read atomically.
+    ///
+    /// Requires `ALC_SOFT_device_clock`.
+    pub fn clock_latency(&self) -> Result<DeviceTime, Error> {
+        let mut values = [0; 2];
+        self.get_integer64(ALC_DEVICE_CLOCK_LATENCY_SOFT, &mut values)
+            .map(|()| DeviceTime {
+                clock: duration_from_nanos(values[0]),
+                latency: duration_from_nanos(values[1]),
+            })
+    }
+
+    pub(crate) fn as_ptr(&self) -> *mut ALCdevice {
+        self.0
+    }
+
+    /// Returns the most recent error raised on this device, if any.
+    pub(crate) fn error(&self) -> Option<Error> {
+        Error::from_alc(unsafe { alcGetError(self.0) })
+    }
+
+    /// Looks up an ALC extension function, returning an error if the extension is not supported.
+    ///
+    /// `F` must be the function pointer type of the named function.
+    pub(crate) unsafe fn extension_function<F: Copy>(
+        &self,
+        extension: &'static str,
+        function: &str,
+    ) -> Result<F, Error> {
+        if !self.is_extension_present(extension) {
+            return Err(Error::ExtensionNotPresent(extension));
+        }
+        let address = util::with_c_str(function, |function| {
+            alcGetProcAddress(self.0, function.cast())
+        });
+        match address {
+            Some(address) if !address.is_null() => Ok(mem::transmute_copy(&address)),
+            _ => Err(Error::ExtensionNotPresent(extension)),
+        }
+    }
+
+    /// Returns the value of an integer device property.
+    pub(crate) fn get_integer(&self, param: ALCenum) -> Result<ALCint, Error> {
+        let mut value = 0;
+        let _ = self.error();
+        unsafe { alcGetIntegerv(self.0, param, 1, &mut value) };
+        self.error().map_or(Ok(value), Err)
+    }
+
+    fn get_integer64(&self, param: ALCenum, values: &mut [ALCint64SOFT]) -> Result<(), Error> {
+        let get: GetInteger64vFn =
+            unsafe { self.extension_function("ALC_SOFT_device_clock", "alcGetInteger64vSOFT")? };
+        let size = ALCsizei::try_from(values.len()).map_err(|_| Error::InvalidValue)?;
+        let _ = self.error();
+        unsafe { get(self.0, param, size, values.as_mut_ptr()) };
+        self.error().map_or(Ok(()), Err)
+    }
+
+    fn from_ptr(device: *mut ALCdevice) -> Result<Self, Error> {
+        if device.is_null() {
+            Err(Error::from_alc(unsafe { alcGetError(ptr::null_mut()) })
+                .unwrap_or(Error::InvalidDevice))
+        } else {
+            Ok(Device(device))
+        }
+    }
+}
+
+impl Drop for Device {
+    fn drop(&mut self) {
+        let _ = unsafe { alcCloseDevice(self.0) };
+    }
+}
+
+/// Converts a time in nanoseconds reported by ALC, clamping negative values to zero.
+fn duration_from_nanos(nanos: ALCint64SOFT) -> Duration {
+    Duration::from_nanos(nanos.max(0) as u64)
+}
@@ -0,0 +1,276 @@
+// Copyright (c) 2018 FaultyRAM
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the
+// MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at
+// your option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use bindgen_openal_sys::{
+    ALint, AL_EXPONENT_DISTANCE, AL_EXPONENT_DISTANCE_CLAMPED, AL_INVERSE_DISTANCE,
+    AL_INVERSE_DISTANCE_CLAMPED, AL_LINEAR_DISTANCE, AL_LINEAR_DISTANCE_CLAMPED, AL_NONE,
+};
+use error::Error;
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+/// The curve used to attenuate sources as they move away from the listener.
+///
+/// The clamped variants limit the distance used in the calculation to between a source's
+/// reference distance and maximum distance.
+pub enum DistanceModel {
+    /// No distance attenuation.
+    None,
+    /// Gain falls off in inverse proportion to distance.
+    Inverse,
+    /// As `Inverse`, with the distance clamped. This is the default.
+    #[default]
+    InverseClamped,
+    /// Gain falls off linearly, reaching zero at the maximum distance.
+    Linear,
+    /// As `Linear`, with the distance clamped.
+    LinearClamped,
+    /// Gain falls off exponentially with distance.
+    Exponent,
+    /// As `Exponent`, with the distance clamped.
+    ExponentClamped,
+}
+
+impl DistanceModel {
+    pub(crate) fn to_al(self) -> ALint {
+        match self {
+            DistanceModel::None => AL_NONE,
+            DistanceModel::Inverse => AL_INVERSE_DISTANCE,
+            DistanceModel::InverseClamped => AL_INVERSE_DISTANCE_CLAMPED,
+            DistanceModel::Linear => AL_LINEAR_DISTANCE,
+            DistanceModel::LinearClamped => AL_LINEAR_DISTANCE_CLAMPED,
+            DistanceModel::Exponent => AL_EXPONENT_DISTANCE,
+            DistanceModel::ExponentClamped => AL_EXPONENT_DISTANCE_CLAMPED,
+        }
+    }
+
+    pub(crate) fn from_al(model: ALint) -> Result<Self, Error> {
+        match model {
+            AL_NONE => Ok(DistanceModel::None),
+            AL_INVERSE_DISTANCE => Ok(DistanceModel::Inverse),
+            AL_INVERSE_DISTANCE_CLAMPED => Ok(DistanceModel::InverseClamped),
+            AL_LINEAR_DISTANCE => Ok(DistanceModel::Linear),
+            AL_LINEAR_DISTANCE_CLAMPED => Ok(DistanceModel::LinearClamped),
+            AL_EXPONENT_DISTANCE => Ok(DistanceModel::Exponent),
+            AL_EXPONENT_DISTANCE_CLAMPED => Ok(DistanceModel::ExponentClamped),
+            _ => Err(Error::InvalidEnum),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The attenuation parameters of a source, used to compute the gain OpenAL would apply to it.
+///
+/// This mirrors the calculations OpenAL Soft performs, without needing a device, so falloff
+/// curves can be inspected offline. The source and listener gains, and the source's minimum and
+/// maximum gain, are applied on top of this by the implementation and are not included.
+pub struct Attenuation {
+    /// The distance model to apply.
+    pub model: DistanceModel,
+    /// The distance at which the distance gain is 1.
+    pub reference_distance: f32,
+    /// How quickly the gain falls off beyond the reference distance.
+    pub rolloff_factor: f32,
+    /// The distance beyond which clamped models stop attenuating, and at which the linear
+    /// models reach zero.
+    pub max_distance: f32,
+    /// The angle, in degrees, of the cone inside which the source is heard at full gain.
+    pub cone_inner_angle: f32,
+    /// The angle, in degrees, of the cone outside which the source is heard at
+    /// `cone_outer_gain`.
+    pub cone_outer_angle: f32,
+    /// The gain applied outside the outer cone.
+    pub cone_outer_gain: f32,
+}
+
+impl Attenuation {
+    /// Returns the gain applied to a source at the given distance from the listener.
+    pub fn distance_gain(&self, distance: f32) -> f32 {
+        let (reference, rolloff, max) = (
+            self.reference_distance,
+            self.rolloff_factor,
+            self.max_distance,
+        );
+        let distance = match self.model {
+            DistanceModel::InverseClamped
+            | DistanceModel::LinearClamped
+            | DistanceModel::ExponentClamped => {
+                if max < reference {
+                    return 1.0;
+                }
+                distance.max(reference).min(max)
+            }
+            _ => distance,
+        };
+        match self.model {
+            DistanceModel::None => 1.0,
+            DistanceModel::Inverse | DistanceModel::InverseClamped => {
+                let scaled = reference + rolloff * (distance - reference);
+                if reference > 0.0 && scaled > 0.0 {
+                    reference / scaled
+                } else {
+                    1.0
+                }
+            }
+            DistanceModel::Linear | DistanceModel::LinearClamped => {
+                if max != reference {
+                    (1.0 - rolloff * (distance - reference) / (max - reference)).max(0.0)
+                } else {
+                    1.0
+                }
+            }
+            DistanceModel::Exponent | DistanceModel::ExponentClamped => {
+                if distance > 0.0 && reference > 0.0 {
+                    libm::powf(distance / reference, -rolloff)
+                } else {
+                    1.0
+                }
+            }
+        }
+    }
+
+    /// Returns the gain applied to a directional source, given the angle in degrees between the
+    /// source's direction and the direction from the source to the listener.
+    pub fn cone_gain(&self, angle: f32) -> f32 {
+        // The cone angles span both sides of the source's direction.
+        let angle = angle.abs() * 2.0;
+        let (inner, outer) = (self.cone_inner_angle, self.cone_outer_angle);
+        if angle <= inner {
+            1.0
+        } else if angle >= outer {
+            self.cone_outer_gain
+        } else {
+            let t = (angle - inner) / (outer - inner);
+            1.0 + (self.cone_outer_gain - 1.0) * t
+        }
+    }
+
+    /// Returns the combined distance and cone gain for a source at the given distance and angle
+    /// from the listener.
+    pub fn gain(&self, distance: f32, angle: f32) -> f32 {
+        self.distance_gain(distance) * self.cone_gain(angle)
+    }
+}
+
+impl Default for Attenuation {
+    /// Returns the parameters of a newly created source in a default context.
+    fn default() -> Self {
+        Self {
+            model: DistanceModel::default(),
+            reference_distance: 1.0,
+            rolloff_factor: 1.0,
+            max_distance: f32::MAX,
+            cone_inner_angle: 360.0,
+            cone_outer_angle: 360.0,
+            cone_outer_gain: 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // These expectations are worked out from the formulas OpenAL Soft uses. Comparing against
+    // output rendered through `ALC_SOFT_loopback` is left for when the crate can open loopback
+    // devices.
+
+    use super::*;
+
+    const MODELS: [DistanceModel; 7] = [
+        DistanceModel::None,
+        DistanceModel::Inverse,
+        DistanceModel::InverseClamped,
+        DistanceModel::Linear,
+        DistanceModel::LinearClamped,
+        DistanceModel::Exponent,
+        DistanceModel::ExponentClamped,
+    ];
+
+    fn attenuation(model: DistanceModel) -> Attenuation {
+        Attenuation {
+            model,
+            max_distance: 10.0,
+            ..Attenuation::default()
+        }
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn distance_gain() {
+        // Gains at distances of 0.5, 4, 5.5 and 20, with a reference distance of 1 and a maximum
+        // distance of 10.
+        let expected = [
+            [1.0, 1.0, 1.0, 1.0],
+            [2.0, 0.25, 1.0 / 5.5, 0.05],
+            [1.0, 0.25, 1.0 / 5.5, 0.1],
+            [1.0 + 0.5 / 9.0, 1.0 - 3.0 / 9.0, 0.5, 0.0],
+            [1.0, 1.0 - 3.0 / 9.0, 0.5, 0.0],
+            [2.0, 0.25, 1.0 / 5.5, 0.05],
+            [1.0, 0.25, 1.0 / 5.5, 0.1],
+        ];
+        for (&model, gains) in MODELS.iter().zip(&expected) {
+            let attenuation = attenuation(model);
+            for (&distance, &gain) in [0.5, 4.0, 5.5, 20.0].iter().zip(gains) {
+                assert_close(attenuation.distance_gain(distance), gain);
+            }
+        }
+    }
+
+    #[test]
+    fn distance_gain_max_below_reference() {
+        for &model in &MODELS {
+            let attenuation = Attenuation {
+                reference_distance: 5.0,
+                max_distance: 2.0,
+                ..attenuation(model)
+            };
+            let expected = match model {
+                DistanceModel::Inverse => 5.0 / 8.0,
+                DistanceModel::Linear => 2.0,
+                DistanceModel::Exponent => 5.0 / 8.0,
+                _ => 1.0,
+            };
+            assert_close(attenuation.distance_gain(8.0), expected);
+        }
+    }
+
+    #[test]
+    fn distance_gain_without_rolloff() {
+        for &model in &MODELS {
+            let attenuation = Attenuation {
+                rolloff_factor: 0.0,
+                ..attenuation(model)
+            };
+            for &distance in &[0.5, 4.0, 20.0] {
+                assert_close(attenuation.distance_gain(distance), 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn cone_gain() {
+        let attenuation = Attenuation {
+            cone_inner_angle: 90.0,
+            cone_outer_angle: 180.0,
+            cone_outer_gain: 0.2,
+            ..Attenuation::default()
+        };
+        assert_close(attenuation.cone_gain(30.0), 1.0);
+        assert_close(attenuation.cone_gain(-45.0), 1.0);
+        assert_close(attenuation.cone_gain(67.5), 0.6);
+        assert_close(attenuation.cone_gain(120.0), 0.2);
+        assert_close(attenuation.gain(4.0, 67.5), 0.15);
+        assert_close(Attenuation::default().cone_gain(180.0), 1.0);
+    }
+}
@@ -0,0 +1,1091 @@
+// Copyright (c) 2018 FaultyRAM
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the
+// MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at
+// your option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use bindgen_openal_sys::{ALenum, ALint, ALuint};
+use context::Context;
+use error::Error;
+use ext::{
+    DeleteEffectsFn, EffectfFn, EffectfvFn, EffectiFn, GenEffectsFn, AL_AUTOWAH_ATTACK_TIME,
+    AL_AUTOWAH_PEAK_GAIN, AL_AUTOWAH_RELEASE_TIME, AL_AUTOWAH_RESONANCE, AL_CHORUS_DELAY,
+    AL_CHORUS_DEPTH, AL_CHORUS_FEEDBACK, AL_CHORUS_PHASE, AL_CHORUS_RATE, AL_CHORUS_WAVEFORM,
+    AL_COMPRESSOR_ONOFF, AL_DISTORTION_EDGE, AL_DISTORTION_EQBANDWIDTH, AL_DISTORTION_EQCENTER,
+    AL_DISTORTION_GAIN, AL_DISTORTION_LOWPASS_CUTOFF, AL_EAXREVERB_AIR_ABSORPTION_GAINHF,
+    AL_EAXREVERB_DECAY_HFLIMIT, AL_EAXREVERB_DECAY_HFRATIO, AL_EAXREVERB_DECAY_LFRATIO,
+    AL_EAXREVERB_DECAY_TIME, AL_EAXREVERB_DENSITY, AL_EAXREVERB_DIFFUSION, AL_EAXREVERB_ECHO_DEPTH,
+    AL_EAXREVERB_ECHO_TIME, AL_EAXREVERB_GAIN, AL_EAXREVERB_GAINHF, AL_EAXREVERB_GAINLF,
+    AL_EAXREVERB_HFREFERENCE, AL_EAXREVERB_LATE_REVERB_DELAY, AL_EAXREVERB_LATE_REVERB_GAIN,
+    AL_EAXREVERB_LATE_REVERB_PAN, AL_EAXREVERB_LFREFERENCE, AL_EAXREVERB_MODULATION_DEPTH,
+    AL_EAXREVERB_MODULATION_TIME, AL_EAXREVERB_REFLECTIONS_DELAY, AL_EAXREVERB_REFLECTIONS_GAIN,
+    AL_EAXREVERB_REFLECTIONS_PAN, AL_EAXREVERB_ROOM_ROLLOFF_FACTOR, AL_ECHO_DAMPING, AL_ECHO_DELAY,
+    AL_ECHO_FEEDBACK, AL_ECHO_LRDELAY, AL_ECHO_SPREAD, AL_EFFECT_AUTOWAH, AL_EFFECT_CHORUS,
+    AL_EFFECT_COMPRESSOR, AL_EFFECT_DISTORTION, AL_EFFECT_EAXREVERB, AL_EFFECT_ECHO,
+    AL_EFFECT_EQUALIZER, AL_EFFECT_FLANGER, AL_EFFECT_FREQUENCY_SHIFTER, AL_EFFECT_PITCH_SHIFTER,
+    AL_EFFECT_REVERB, AL_EFFECT_RING_MODULATOR, AL_EFFECT_TYPE, AL_EFFECT_VOCAL_MORPHER,
+    AL_EQUALIZER_HIGH_CUTOFF, AL_EQUALIZER_HIGH_GAIN, AL_EQUALIZER_LOW_CUTOFF,
+    AL_EQUALIZER_LOW_GAIN, AL_EQUALIZER_MID1_CENTER, AL_EQUALIZER_MID1_GAIN,
+    AL_EQUALIZER_MID1_WIDTH, AL_EQUALIZER_MID2_CENTER, AL_EQUALIZER_MID2_GAIN,
+    AL_EQUALIZER_MID2_WIDTH, AL_FLANGER_DELAY, AL_FLANGER_DEPTH, AL_FLANGER_FEEDBACK,
+    AL_FLANGER_PHASE, AL_FLANGER_RATE, AL_FLANGER_WAVEFORM, AL_FREQUENCY_SHIFTER_FREQUENCY,
+    AL_FREQUENCY_SHIFTER_LEFT_DIRECTION, AL_FREQUENCY_SHIFTER_RIGHT_DIRECTION,
+    AL_PITCH_SHIFTER_COARSE_TUNE, AL_PITCH_SHIFTER_FINE_TUNE, AL_REVERB_AIR_ABSORPTION_GAINHF,
+    AL_REVERB_DECAY_HFLIMIT, AL_REVERB_DECAY_HFRATIO, AL_REVERB_DECAY_TIME, AL_REVERB_DENSITY,
+    AL_REVERB_DIFFUSION, AL_REVERB_GAIN, AL_REVERB_GAINHF, AL_REVERB_LATE_REVERB_DELAY,
+    AL_REVERB_LATE_REVERB_GAIN, AL_REVERB_REFLECTIONS_DELAY, AL_REVERB_REFLECTIONS_GAIN,
+    AL_REVERB_ROOM_ROLLOFF_FACTOR, AL_RING_MODULATOR_FREQUENCY, AL_RING_MODULATOR_HIGHPASS_CUTOFF,
+    AL_RING_MODULATOR_WAVEFORM, AL_VOCAL_MORPHER_PHONEMEA, AL_VOCAL_MORPHER_PHONEMEA_COARSE_TUNING,
+    AL_VOCAL_MORPHER_PHONEMEB, AL_VOCAL_MORPHER_PHONEMEB_COARSE_TUNING, AL_VOCAL_MORPHER_RATE,
+    AL_VOCAL_MORPHER_WAVEFORM,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The type of an effect, along with its parameters.
+pub enum EffectType {
+    /// A standard reverb.
+    Reverb(Reverb),
+    /// A reverb with the additional controls of EAX 4.0.
+    EaxReverb(EaxReverb),
+    /// Layers copies of the input with slightly varying delays.
+    Chorus(Chorus),
+    /// Clips and shapes the input.
+    Distortion(Distortion),
+    /// Repeats the input with a delay.
+    Echo(Echo),
+    /// Mixes the input with a copy whose delay sweeps up and down.
+    Flanger(Flanger),
+    /// Shifts every frequency of the input by a fixed amount.
+    FrequencyShifter(FrequencyShifter),
+    /// Filters the input through a pair of vocal formants.
+    VocalMorpher(VocalMorpher),
+    /// Shifts the pitch of the input.
+    PitchShifter(PitchShifter),
+    /// Multiplies the input by a carrier signal.
+    RingModulator(RingModulator),
+    /// Sweeps a resonant band-pass filter with the input's level.
+    Autowah(Autowah),
+    /// Evens out the input's level.
+    Compressor(Compressor),
+    /// A four-band equalizer.
+    Equalizer(Equalizer),
+}
+
+impl EffectType {
+    fn to_al(self) -> ALint {
+        match self {
+            EffectType::Reverb(_) => AL_EFFECT_REVERB,
+            EffectType::EaxReverb(_) => AL_EFFECT_EAXREVERB,
+            EffectType::Chorus(_) => AL_EFFECT_CHORUS,
+            EffectType::Distortion(_) => AL_EFFECT_DISTORTION,
+            EffectType::Echo(_) => AL_EFFECT_ECHO,
+            EffectType::Flanger(_) => AL_EFFECT_FLANGER,
+            EffectType::FrequencyShifter(_) => AL_EFFECT_FREQUENCY_SHIFTER,
+            EffectType::VocalMorpher(_) => AL_EFFECT_VOCAL_MORPHER,
+            EffectType::PitchShifter(_) => AL_EFFECT_PITCH_SHIFTER,
+            EffectType::RingModulator(_) => AL_EFFECT_RING_MODULATOR,
+            EffectType::Autowah(_) => AL_EFFECT_AUTOWAH,
+            EffectType::Compressor(_) => AL_EFFECT_COMPRESSOR,
+            EffectType::Equalizer(_) => AL_EFFECT_EQUALIZER,
+        }
+    }
+
+    /// Passes each parameter, along with its valid range, to `f`.
+    fn visit(&self, f: &mut dyn FnMut(ALenum, Param) -> Result<(), Error>) -> Result<(), Error> {
+        match *self {
+            EffectType::Reverb(ref params) => params.visit(f),
+            EffectType::EaxReverb(ref params) => params.visit(f),
+            EffectType::Chorus(ref params) => params.visit(f),
+            EffectType::Distortion(ref params) => params.visit(f),
+            EffectType::Echo(ref params) => params.visit(f),
+            EffectType::Flanger(ref params) => params.visit(f),
+            EffectType::FrequencyShifter(ref params) => params.visit(f),
+            EffectType::VocalMorpher(ref params) => params.visit(f),
+            EffectType::PitchShifter(ref params) => params.visit(f),
+            EffectType::RingModulator(ref params) => params.visit(f),
+            EffectType::Autowah(ref params) => params.visit(f),
+            EffectType::Compressor(ref params) => params.visit(f),
+            EffectType::Equalizer(ref params) => params.visit(f),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+/// The shape of a low-frequency oscillator.
+pub enum Waveform {
+    /// A sine wave.
+    Sinusoid,
+    /// A triangle wave.
+    Triangle,
+    /// A sawtooth wave. Not supported by `Chorus` or `Flanger`.
+    Sawtooth,
+}
+
+impl Waveform {
+    fn to_al(self) -> ALint {
+        match self {
+            Waveform::Sinusoid => 0,
+            Waveform::Triangle => 1,
+            Waveform::Sawtooth => 2,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+/// The shape of a ring modulator's carrier signal.
+pub enum RingModulatorWaveform {
+    /// A sine wave.
+    Sinusoid,
+    /// A sawtooth wave.
+    Sawtooth,
+    /// A square wave.
+    Square,
+}
+
+impl RingModulatorWaveform {
+    fn to_al(self) -> ALint {
+        match self {
+            RingModulatorWaveform::Sinusoid => 0,
+            RingModulatorWaveform::Sawtooth => 1,
+            RingModulatorWaveform::Square => 2,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+/// The direction in which a frequency shifter shifts one channel.
+pub enum ShiftDirection {
+    /// Frequencies are shifted down.
+    Down,
+    /// Frequencies are shifted up.
+    Up,
+    /// The channel is passed through unchanged.
+    Off,
+}
+
+impl ShiftDirection {
+    fn to_al(self) -> ALint {
+        match self {
+            ShiftDirection::Down => 0,
+            ShiftDirection::Up => 1,
+            ShiftDirection::Off => 2,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+/// A vowel or consonant formant used by the vocal morpher.
+pub enum Phoneme {
+    /// The phoneme "A".
+    A,
+    /// The phoneme "E".
+    E,
+    /// The phoneme "I".
+    I,
+    /// The phoneme "O".
+    O,
+    /// The phoneme "U".
+    U,
+    /// The phoneme "AA".
+    Aa,
+    /// The phoneme "AE".
+    Ae,
+    /// The phoneme "AH".
+    Ah,
+    /// The phoneme "AO".
+    Ao,
+    /// The phoneme "EH".
+    Eh,
+    /// The phoneme "ER".
+    Er,
+    /// The phoneme "IH".
+    Ih,
+    /// The phoneme "IY".
+    Iy,
+    /// The phoneme "UH".
+    Uh,
+    /// The phoneme "UW".
+    Uw,
+    /// The phoneme "B".
+    B,
+    /// The phoneme "D".
+    D,
+    /// The phoneme "F".
+    F,
+    /// The phoneme "G".
+    G,
+    /// The phoneme "J".
+    J,
+    /// The phoneme "K".
+    K,
+    /// The phoneme "L".
+    L,
+    /// The phoneme "M".
+    M,
+    /// The phoneme "N".
+    N,
+    /// The phoneme "P".
+    P,
+    /// The phoneme "R".
+    R,
+    /// The phoneme "S".
+    S,
+    /// The phoneme "T".
+    T,
+    /// The phoneme "V".
+    V,
+    /// The phoneme "Z".
+    Z,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The parameters of a standard reverb.
+pub struct Reverb {
+    /// The modal density of the late reverb, from 0 to 1.
+    pub density: f32,
+    /// The echo density of the late reverb, from 0 to 1.
+    pub diffusion: f32,
+    /// The overall level of the reverb, from 0 to 1.
+    pub gain: f32,
+    /// The high-frequency level of the reverb, from 0 to 1.
+    pub gain_hf: f32,
+    /// The decay time of the late reverb in seconds, from 0.1 to 20.
+    pub decay_time: f32,
+    /// The ratio of high-frequency to mid-frequency decay time, from 0.1 to 2.
+    pub decay_hf_ratio: f32,
+    /// The level of the early reflections, from 0 to 3.16.
+    pub reflections_gain: f32,
+    /// The delay before the early reflections in seconds, from 0 to 0.3.
+    pub reflections_delay: f32,
+    /// The level of the late reverb, from 0 to 10.
+    pub late_reverb_gain: f32,
+    /// The delay between the early reflections and the late reverb in seconds, from 0 to 0.1.
+    pub late_reverb_delay: f32,
+    /// The high-frequency attenuation applied per metre by air, from 0.892 to 1.
+    pub air_absorption_gain_hf: f32,
+    /// The rolloff factor applied to the reverb, from 0 to 10.
+    pub room_rolloff_factor: f32,
+    /// Whether the high-frequency decay time is limited by air absorption.
+    pub decay_hf_limit: bool,
+}
+
+impl Reverb {
+    fn visit(&self, f: &mut dyn FnMut(ALenum, Param) -> Result<(), Error>) -> Result<(), Error> {
+        f(AL_REVERB_DENSITY, Param::Float(self.density, 0.0, 1.0))?;
+        f(AL_REVERB_DIFFUSION, Param::Float(self.diffusion, 0.0, 1.0))?;
+        f(AL_REVERB_GAIN, Param::Float(self.gain, 0.0, 1.0))?;
+        f(AL_REVERB_GAINHF, Param::Float(self.gain_hf, 0.0, 1.0))?;
+        f(
+            AL_REVERB_DECAY_TIME,
+            Param::Float(self.decay_time, 0.1, 20.0),
+        )?;
+        f(
+            AL_REVERB_DECAY_HFRATIO,
+            Param::Float(self.decay_hf_ratio, 0.1, 2.0),
+        )?;
+        f(
+            AL_REVERB_REFLECTIONS_GAIN,
+            Param::Float(self.reflections_gain, 0.0, 3.16),
+        )?;
+        f(
+            AL_REVERB_REFLECTIONS_DELAY,
+            Param::Float(self.reflections_delay, 0.0, 0.3),
+        )?;
+        f(
+            AL_REVERB_LATE_REVERB_GAIN,
+            Param::Float(self.late_reverb_gain, 0.0, 10.0),
+        )?;
+        f(
+            AL_REVERB_LATE_REVERB_DELAY,
+            Param::Float(self.late_reverb_delay, 0.0, 0.1),
+        )?;
+        f(
+            AL_REVERB_AIR_ABSORPTION_GAINHF,
+            Param::Float(self.air_absorption_gain_hf, 0.892, 1.0),
+        )?;
+        f(
+            AL_REVERB_ROOM_ROLLOFF_FACTOR,
+            Param::Float(self.room_rolloff_factor, 0.0, 10.0),
+        )?;
+        f(AL_REVERB_DECAY_HFLIMIT, Param::Bool(self.decay_hf_limit))
+    }
+}
+
+impl Default for Reverb {
+    fn default() -> Self {
+        Self {
+            density: 1.0,
+            diffusion: 1.0,
+            gain: 0.32,
+            gain_hf: 0.89,
+            decay_time: 1.49,
+            decay_hf_ratio: 0.83,
+            reflections_gain: 0.05,
+            reflections_delay: 0.007,
+            late_reverb_gain: 1.26,
+            late_reverb_delay: 0.011,
+            air_absorption_gain_hf: 0.994,
+            room_rolloff_factor: 0.0,
+            decay_hf_limit: true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The parameters of an EAX reverb.
+pub struct EaxReverb {
+    /// The modal density of the late reverb, from 0 to 1.
+    pub density: f32,
+    /// The echo density of the late reverb, from 0 to 1.
+    pub diffusion: f32,
+    /// The overall level of the reverb, from 0 to 1.
+    pub gain: f32,
+    /// The high-frequency level of the reverb, from 0 to 1.
+    pub gain_hf: f32,
+    /// The low-frequency level of the reverb, from 0 to 1.
+    pub gain_lf: f32,
+    /// The decay time of the late reverb in seconds, from 0.1 to 20.
+    pub decay_time: f32,
+    /// The ratio of high-frequency to mid-frequency decay time, from 0.1 to 2.
+    pub decay_hf_ratio: f32,
+    /// The ratio of low-frequency to mid-frequency decay time, from 0.1 to 2.
+    pub decay_lf_ratio: f32,
+    /// The level of the early reflections, from 0 to 3.16.
+    pub reflections_gain: f32,
+    /// The delay before the early reflections in seconds, from 0 to 0.3.
+    pub reflections_delay: f32,
+    /// The direction of the early reflections, with a length of at most 1.
+    pub reflections_pan: [f32; 3],
+    /// The level of the late reverb, from 0 to 10.
+    pub late_reverb_gain: f32,
+    /// The delay between the early reflections and the late reverb in seconds, from 0 to 0.1.
+    pub late_reverb_delay: f32,
+    /// The direction of the late reverb, with a length of at most 1.
+    pub late_reverb_pan: [f32; 3],
+    /// The period of the late reverb's cyclic echo in seconds, from 0.075 to 0.25.
+    pub echo_time: f32,
+    /// The strength of the late reverb's cyclic echo, from 0 to 1.
+    pub echo_depth: f32,
+    /// The period of the late reverb's pitch modulation in seconds, from 0.04 to 4.
+    pub modulation_time: f32,
+    /// The strength of the late reverb's pitch modulation, from 0 to 1.
+    pub modulation_depth: f32,
+    /// The high-frequency attenuation applied per metre by air, from 0.892 to 1.
+    pub air_absorption_gain_hf: f32,
+    /// The frequency above which `gain_hf` applies in hertz, from 1000 to 20000.
+    pub hf_reference: f32,
+    /// The frequency below which `gain_lf` applies in hertz, from 20 to 1000.
+    pub lf_reference: f32,
+    /// The rolloff factor applied to the reverb, from 0 to 10.
+    pub room_rolloff_factor: f32,
+    /// Whether the high-frequency decay time is limited by air absorption.
+    pub decay_hf_limit: bool,
+}
+
+impl EaxReverb {
+    fn visit(&self, f: &mut dyn FnMut(ALenum, Param) -> Result<(), Error>) -> Result<(), Error> {
+        f(AL_EAXREVERB_DENSITY, Param::Float(self.density, 0.0, 1.0))?;
+        f(
+            AL_EAXREVERB_DIFFUSION,
+            Param::Float(self.diffusion, 0.0, 1.0),
+        )?;
+        f(AL_EAXREVERB_GAIN, Param::Float(self.gain, 0.0, 1.0))?;
+        f(AL_EAXREVERB_GAINHF, Param::Float(self.gain_hf, 0.0, 1.0))?;
+        f(AL_EAXREVERB_GAINLF, Param::Float(self.gain_lf, 0.0, 1.0))?;
+        f(
+            AL_EAXREVERB_DECAY_TIME,
+            Param::Float(self.decay_time, 0.1, 20.0),
+        )?;
+        f(
+            AL_EAXREVERB_DECAY_HFRATIO,
+            Param::Float(self.decay_hf_ratio, 0.1, 2.0),
+        )?;
+        f(
+            AL_EAXREVERB_DECAY_LFRATIO,
+            Param::Float(self.decay_lf_ratio, 0.1, 2.0),
+        )?;
+        f(
+            AL_EAXREVERB_REFLECTIONS_GAIN,
+            Param::Float(self.reflections_gain, 0.0, 3.16),
+        )?;
+        f(
+            AL_EAXREVERB_REFLECTIONS_DELAY,
+            Param::Float(self.reflections_delay, 0.0, 0.3),
+        )?;
+        f(
+            AL_EAXREVERB_REFLECTIONS_PAN,
+            Param::Vector(self.reflections_pan),
+        )?;
+        f(
+            AL_EAXREVERB_LATE_REVERB_GAIN,
+            Param::Float(self.late_reverb_gain, 0.0, 10.0),
+        )?;
+        f(
+            AL_EAXREVERB_LATE_REVERB_DELAY,
+            Param::Float(self.late_reverb_delay, 0.0, 0.1),
+        )?;
+        f(
+            AL_EAXREVERB_LATE_REVERB_PAN,
+            Param::Vector(self.late_reverb_pan),
+        )?;
+        f(
+            AL_EAXREVERB_ECHO_TIME,
+            Param::Float(self.echo_time, 0.075, 0.25),
+        )?;
+        f(
+            AL_EAXREVERB_ECHO_DEPTH,
+            Param::Float(self.echo_depth, 0.0, 1.0),
+        )?;
+        f(
+            AL_EAXREVERB_MODULATION_TIME,
+            Param::Float(self.modulation_time, 0.04, 4.0),
+        )?;
+        f(
+            AL_EAXREVERB_MODULATION_DEPTH,
+            Param::Float(self.modulation_depth, 0.0, 1.0),
+        )?;
+        f(
+            AL_EAXREVERB_AIR_ABSORPTION_GAINHF,
+            Param::Float(self.air_absorption_gain_hf, 0.892, 1.0),
+        )?;
+        f(
+            AL_EAXREVERB_HFREFERENCE,
+            Param::Float(self.hf_reference, 1000.0, 20000.0),
+        )?;
+        f(
+            AL_EAXREVERB_LFREFERENCE,
+            Param::Float(self.lf_reference, 20.0, 1000.0),
+        )?;
+        f(
+            AL_EAXREVERB_ROOM_ROLLOFF_FACTOR,
+            Param::Float(self.room_rolloff_factor, 0.0, 10.0),
+        )?;
+        f(AL_EAXREVERB_DECAY_HFLIMIT, Param::Bool(self.decay_hf_limit))
+    }
+}
+
+impl Default for EaxReverb {
+    fn default() -> Self {
+        Self {
+            density: 1.0,
+            diffusion: 1.0,
+            gain: 0.32,
+            gain_hf: 0.89,
+            gain_lf: 1.0,
+            decay_time: 1.49,
+            decay_hf_ratio: 0.83,
+            decay_lf_ratio: 1.0,
+            reflections_gain: 0.05,
+            reflections_delay: 0.007,
+            reflections_pan: [0.0; 3],
+            late_reverb_gain: 1.26,
+            late_reverb_delay: 0.011,
+            late_reverb_pan: [0.0; 3],
+            echo_time: 0.25,
+            echo_depth: 0.0,
+            modulation_time: 0.25,
+            modulation_depth: 0.0,
+            air_absorption_gain_hf: 0.994,
+            hf_reference: 5000.0,
+            lf_reference: 250.0,
+            room_rolloff_factor: 0.0,
+            decay_hf_limit: true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The parameters of a chorus.
+pub struct Chorus {
+    /// The shape of the delay modulation. Must be `Sinusoid` or `Triangle`.
+    pub waveform: Waveform,
+    /// The phase difference between the left and right modulation in degrees, from -180 to 180.
+    pub phase: i32,
+    /// The modulation rate in hertz, from 0 to 10.
+    pub rate: f32,
+    /// The modulation depth, from 0 to 1.
+    pub depth: f32,
+    /// The amount of output fed back into the input, from -1 to 1.
+    pub feedback: f32,
+    /// The average delay in seconds, from 0 to 0.016.
+    pub delay: f32,
+}
+
+impl Chorus {
+    fn visit(&self, f: &mut dyn FnMut(ALenum, Param) -> Result<(), Error>) -> Result<(), Error> {
+        f(AL_CHORUS_WAVEFORM, Param::Int(self.waveform.to_al(), 0, 1))?;
+        f(AL_CHORUS_PHASE, Param::Int(self.phase, -180, 180))?;
+        f(AL_CHORUS_RATE, Param::Float(self.rate, 0.0, 10.0))?;
+        f(AL_CHORUS_DEPTH, Param::Float(self.depth, 0.0, 1.0))?;
+        f(AL_CHORUS_FEEDBACK, Param::Float(self.feedback, -1.0, 1.0))?;
+        f(AL_CHORUS_DELAY, Param::Float(self.delay, 0.0, 0.016))
+    }
+}
+
+impl Default for Chorus {
+    fn default() -> Self {
+        Self {
+            waveform: Waveform::Triangle,
+            phase: 90,
+            rate: 1.1,
+            depth: 0.1,
+            feedback: 0.25,
+            delay: 0.016,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The parameters of a distortion.
+pub struct Distortion {
+    /// The shape of the distortion curve, from 0 to 1.
+    pub edge: f32,
+    /// The level of the output, from 0.01 to 1.
+    pub gain: f32,
+    /// The cutoff of the low-pass filter applied before distortion in hertz, from 80 to 24000.
+    pub lowpass_cutoff: f32,
+    /// The centre of the band boosted after distortion in hertz, from 80 to 24000.
+    pub eq_center: f32,
+    /// The width of the band boosted after distortion in hertz, from 80 to 24000.
+    pub eq_bandwidth: f32,
+}
+
+impl Distortion {
+    fn visit(&self, f: &mut dyn FnMut(ALenum, Param) -> Result<(), Error>) -> Result<(), Error> {
+        f(AL_DISTORTION_EDGE, Param::Float(self.edge, 0.0, 1.0))?;
+        f(AL_DISTORTION_GAIN, Param::Float(self.gain, 0.01, 1.0))?;
+        f(
+            AL_DISTORTION_LOWPASS_CUTOFF,
+            Param::Float(self.lowpass_cutoff, 80.0, 24000.0),
+        )?;
+        f(
+            AL_DISTORTION_EQCENTER,
+            Param::Float(self.eq_center, 80.0, 24000.0),
+        )?;
+        f(
+            AL_DISTORTION_EQBANDWIDTH,
+            Param::Float(self.eq_bandwidth, 80.0, 24000.0),
+        )
+    }
+}
+
+impl Default for Distortion {
+    fn default() -> Self {
+        Self {
+            edge: 0.2,
+            gain: 0.05,
+            lowpass_cutoff: 8000.0,
+            eq_center: 3600.0,
+            eq_bandwidth: 3600.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The parameters of an echo.
+pub struct Echo {
+    /// The delay of the first echo in seconds, from 0 to 0.207.
+    pub delay: f32,
+    /// The delay between the first and second echoes in seconds, from 0 to 0.404.
+    pub lr_delay: f32,
+    /// The high-frequency damping applied to each echo, from 0 to 0.99.
+    pub damping: f32,
+    /// The amount of output fed back into the input, from 0 to 1.
+    pub feedback: f32,
+    /// How far apart the echoes are panned, from -1 to 1.
+    pub spread: f32,
+}
+
+impl Echo {
+    fn visit(&self, f: &mut dyn FnMut(ALenum, Param) -> Result<(), Error>) -> Result<(), Error> {
+        f(AL_ECHO_DELAY, Param::Float(self.delay, 0.0, 0.207))?;
+        f(AL_ECHO_LRDELAY, Param::Float(self.lr_delay, 0.0, 0.404))?;
+        f(AL_ECHO_DAMPING, Param::Float(self.damping, 0.0, 0.99))?;
+        f(AL_ECHO_FEEDBACK, Param::Float(self.feedback, 0.0, 1.0))?;
+        f(AL_ECHO_SPREAD, Param::Float(self.spread, -1.0, 1.0))
+    }
+}
+
+impl Default for Echo {
+    fn default() -> Self {
+        Self {
+            delay: 0.1,
+            lr_delay: 0.1,
+            damping: 0.5,
+            feedback: 0.5,
+            spread: -1.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The parameters of a flanger.
+pub struct Flanger {
+    /// The shape of the delay modulation. Must be `Sinusoid` or `Triangle`.
+    pub waveform: Waveform,
+    /// The phase difference between the left and right modulation in degrees, from -180 to 180.
+    pub phase: i32,
+    /// The modulation rate in hertz, from 0 to 10.
+    pub rate: f32,
+    /// The modulation depth, from 0 to 1.
+    pub depth: f32,
+    /// The amount of output fed back into the input, from -1 to 1.
+    pub feedback: f32,
+    /// The average delay in seconds, from 0 to 0.004.
+    pub delay: f32,
+}
+
+impl Flanger {
+    fn visit(&self, f: &mut dyn FnMut(ALenum, Param) -> Result<(), Error>) -> Result<(), Error> {
+        f(AL_FLANGER_WAVEFORM, Param::Int(self.waveform.to_al(), 0, 1))?;
+        f(AL_FLANGER_PHASE, Param::Int(self.phase, -180, 180))?;
+        f(AL_FLANGER_RATE, Param::Float(self.rate, 0.0, 10.0))?;
+        f(AL_FLANGER_DEPTH, Param::Float(self.depth, 0.0, 1.0))?;
+        f(AL_FLANGER_FEEDBACK, Param::Float(self.feedback, -1.0, 1.0))?;
+        f(AL_FLANGER_DELAY, Param::Float(self.delay, 0.0, 0.004))
+    }
+}
+
+impl Default for Flanger {
+    fn default() -> Self {
+        Self {
+            waveform: Waveform::Triangle,
+            phase: 0,
+            rate: 0.27,
+            depth: 1.0,
+            feedback: -0.5,
+            delay: 0.002,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The parameters of a frequency shifter.
+pub struct FrequencyShifter {
+    /// The amount to shift by in hertz, from 0 to 24000.
+    pub frequency: f32,
+    /// The direction in which the left channel is shifted.
+    pub left_direction: ShiftDirection,
+    /// The direction in which the right channel is shifted.
+    pub right_direction: ShiftDirection,
+}
+
+impl FrequencyShifter {
+    fn visit(&self, f: &mut dyn FnMut(ALenum, Param) -> Result<(), Error>) -> Result<(), Error> {
+        f(
+            AL_FREQUENCY_SHIFTER_FREQUENCY,
+            Param::Float(self.frequency, 0.0, 24000.0),
+        )?;
+        f(
+            AL_FREQUENCY_SHIFTER_LEFT_DIRECTION,
+            Param::Int(self.left_direction.to_al(), 0, 2),
+        )?;
+        f(
+            AL_FREQUENCY_SHIFTER_RIGHT_DIRECTION,
+            Param::Int(self.right_direction.to_al(), 0, 2),
+        )
+    }
+}
+
+impl Default for FrequencyShifter {
+    fn default() -> Self {
+        Self {
+            frequency: 0.0,
+            left_direction: ShiftDirection::Down,
+            right_direction: ShiftDirection::Down,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The parameters of a vocal morpher.
+pub struct VocalMorpher {
+    /// The first formant.
+    pub phoneme_a: Phoneme,
+    /// The tuning of the first formant in semitones, from -24 to 24.
+    pub phoneme_a_coarse_tuning: i32,
+    /// The second formant.
+    pub phoneme_b: Phoneme,
+    /// The tuning of the second formant in semitones, from -24 to 24.
+    pub phoneme_b_coarse_tuning: i32,
+    /// The shape of the morph between the two formants.
+    pub waveform: Waveform,
+    /// The morph rate in hertz, from 0 to 10.
+    pub rate: f32,
+}
+
+impl VocalMorpher {
+    fn visit(&self, f: &mut dyn FnMut(ALenum, Param) -> Result<(), Error>) -> Result<(), Error> {
+        f(
+            AL_VOCAL_MORPHER_PHONEMEA,
+            Param::Int(self.phoneme_a as ALint, 0, 29),
+        )?;
+        f(
+            AL_VOCAL_MORPHER_PHONEMEA_COARSE_TUNING,
+            Param::Int(self.phoneme_a_coarse_tuning, -24, 24),
+        )?;
+        f(
+            AL_VOCAL_MORPHER_PHONEMEB,
+            Param::Int(self.phoneme_b as ALint, 0, 29),
+        )?;
+        f(
+            AL_VOCAL_MORPHER_PHONEMEB_COARSE_TUNING,
+            Param::Int(self.phoneme_b_coarse_tuning, -24, 24),
+        )?;
+        f(
+            AL_VOCAL_MORPHER_WAVEFORM,
+            Param::Int(self.waveform.to_al(), 0, 2),
+        )?;
+        f(AL_VOCAL_MORPHER_RATE, Param::Float(self.rate, 0.0, 10.0))
+    }
+}
+
+impl Default for VocalMorpher {
+    fn default() -> Self {
+        Self {
+            phoneme_a: Phoneme::A,
+            phoneme_a_coarse_tuning: 0,
+            phoneme_b: Phoneme::Er,
+            phoneme_b_coarse_tuning: 0,
+            waveform: Waveform::Sinusoid,
+            rate: 1.41,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+/// The parameters of a pitch shifter.
+pub struct PitchShifter {
+    /// The shift in semitones, from -12 to 12.
+    pub coarse_tune: i32,
+    /// The shift in cents, from -50 to 50.
+    pub fine_tune: i32,
+}
+
+impl PitchShifter {
+    fn visit(&self, f: &mut dyn FnMut(ALenum, Param) -> Result<(), Error>) -> Result<(), Error> {
+        f(
+            AL_PITCH_SHIFTER_COARSE_TUNE,
+            Param::Int(self.coarse_tune, -12, 12),
+        )?;
+        f(
+            AL_PITCH_SHIFTER_FINE_TUNE,
+            Param::Int(self.fine_tune, -50, 50),
+        )
+    }
+}
+
+impl Default for PitchShifter {
+    fn default() -> Self {
+        Self {
+            coarse_tune: 12,
+            fine_tune: 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The parameters of a ring modulator.
+pub struct RingModulator {
+    /// The carrier frequency in hertz, from 0 to 8000.
+    pub frequency: f32,
+    /// The cutoff of the high-pass filter applied to the input in hertz, from 0 to 24000.
+    pub highpass_cutoff: f32,
+    /// The shape of the carrier signal.
+    pub waveform: RingModulatorWaveform,
+}
+
+impl RingModulator {
+    fn visit(&self, f: &mut dyn FnMut(ALenum, Param) -> Result<(), Error>) -> Result<(), Error> {
+        f(
+            AL_RING_MODULATOR_FREQUENCY,
+            Param::Float(self.frequency, 0.0, 8000.0),
+        )?;
+        f(
+            AL_RING_MODULATOR_HIGHPASS_CUTOFF,
+            Param::Float(self.highpass_cutoff, 0.0, 24000.0),
+        )?;
+        f(
+            AL_RING_MODULATOR_WAVEFORM,
+            Param::Int(self.waveform.to_al(), 0, 2),
+        )
+    }
+}
+
+impl Default for RingModulator {
+    fn default() -> Self {
+        Self {
+            frequency: 440.0,
+            highpass_cutoff: 800.0,
+            waveform: RingModulatorWaveform::Sinusoid,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The parameters of an auto-wah.
+pub struct Autowah {
+    /// The time taken to respond to a rise in level in seconds, from 0.0001 to 1.
+    pub attack_time: f32,
+    /// The time taken to respond to a fall in level in seconds, from 0.0001 to 1.
+    pub release_time: f32,
+    /// The resonance of the filter, from 2 to 1000.
+    pub resonance: f32,
+    /// The level of the filter's peak, from 0.00003 to 31621.
+    pub peak_gain: f32,
+}
+
+impl Autowah {
+    fn visit(&self, f: &mut dyn FnMut(ALenum, Param) -> Result<(), Error>) -> Result<(), Error> {
+        f(
+            AL_AUTOWAH_ATTACK_TIME,
+            Param::Float(self.attack_time, 0.0001, 1.0),
+        )?;
+        f(
+            AL_AUTOWAH_RELEASE_TIME,
+            Param::Float(self.release_time, 0.0001, 1.0),
+        )?;
+        f(
+            AL_AUTOWAH_RESONANCE,
+            Param::Float(self.resonance, 2.0, 1000.0),
+        )?;
+        f(
+            AL_AUTOWAH_PEAK_GAIN,
+            Param::Float(self.peak_gain, 0.00003, 31621.0),
+        )
+    }
+}
+
+impl Default for Autowah {
+    fn default() -> Self {
+        Self {
+            attack_time: 0.06,
+            release_time: 0.06,
+            resonance: 1000.0,
+            peak_gain: 11.22,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+/// The parameters of a compressor.
+pub struct Compressor {
+    /// Whether compression is applied.
+    pub enabled: bool,
+}
+
+impl Compressor {
+    fn visit(&self, f: &mut dyn FnMut(ALenum, Param) -> Result<(), Error>) -> Result<(), Error> {
+        f(AL_COMPRESSOR_ONOFF, Param::Bool(self.enabled))
+    }
+}
+
+impl Default for Compressor {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The parameters of a four-band equalizer.
+pub struct Equalizer {
+    /// The level of the low band, from 0.126 to 7.943.
+    pub low_gain: f32,
+    /// The upper edge of the low band in hertz, from 50 to 800.
+    pub low_cutoff: f32,
+    /// The level of the first mid band, from 0.126 to 7.943.
+    pub mid1_gain: f32,
+    /// The centre of the first mid band in hertz, from 200 to 3000.
+    pub mid1_center: f32,
+    /// The width of the first mid band in octaves, from 0.01 to 1.
+    pub mid1_width: f32,
+    /// The level of the second mid band, from 0.126 to 7.943.
+    pub mid2_gain: f32,
+    /// The centre of the second mid band in hertz, from 1000 to 8000.
+    pub mid2_center: f32,
+    /// The width of the second mid band in octaves, from 0.01 to 1.
+    pub mid2_width: f32,
+    /// The level of the high band, from 0.126 to 7.943.
+    pub high_gain: f32,
+    /// The lower edge of the high band in hertz, from 4000 to 16000.
+    pub high_cutoff: f32,
+}
+
+impl Equalizer {
+    fn visit(&self, f: &mut dyn FnMut(ALenum, Param) -> Result<(), Error>) -> Result<(), Error> {
+        f(
+            AL_EQUALIZER_LOW_GAIN,
+            Param::Float(self.low_gain, 0.126, 7.943),
+        )?;
+        f(
+            AL_EQUALIZER_LOW_CUTOFF,
+            Param::Float(self.low_cutoff, 50.0, 800.0),
+        )?;
+        f(
+            AL_EQUALIZER_MID1_GAIN,
+            Param::Float(self.mid1_gain, 0.126, 7.943),
+        )?;
+        f(
+            AL_EQUALIZER_MID1_CENTER,
+            Param::Float(self.mid1_center, 200.0, 3000.0),
+        )?;
+        f(
+            AL_EQUALIZER_MID1_WIDTH,
+            Param::Float(self.mid1_width, 0.01, 1.0),
+        )?;
+        f(
+            AL_EQUALIZER_MID2_GAIN,
+            Param::Float(self.mid2_gain, 0.126, 7.943),
+        )?;
+        f(
+            AL_EQUALIZER_MID2_CENTER,
+            Param::Float(self.mid2_center, 1000.0, 8000.0),
+        )?;
+        f(
+            AL_EQUALIZER_MID2_WIDTH,
+            Param::Float(self.mid2_width, 0.01, 1.0),
+        )?;
+        f(
+            AL_EQUALIZER_HIGH_GAIN,
+            Param::Float(self.high_gain, 0.126, 7.943),
+        )?;
+        f(
+            AL_EQUALIZER_HIGH_CUTOFF,
+            Param::Float(self.high_cutoff, 4000.0, 16000.0),
+        )
+    }
+}
+
+impl Default for Equalizer {
+    fn default() -> Self {
+        Self {
+            low_gain: 1.0,
+            low_cutoff: 200.0,
+            mid1_gain: 1.0,
+            mid1_center: 500.0,
+            mid1_width: 1.0,
+            mid2_gain: 1.0,
+            mid2_center: 3000.0,
+            mid2_width: 1.0,
+            high_gain: 1.0,
+            high_cutoff: 6000.0,
+        }
+    }
+}
+
+/// A single effect parameter, along with its valid range.
+enum Param {
+    Float(f32, f32, f32),
+    Int(ALint, ALint, ALint),
+    Bool(bool),
+    /// A vector whose length may not exceed 1.
+    Vector([f32; 3]),
+}
+
+impl Param {
+    fn validate(&self) -> Result<(), Error> {
+        let valid = match *self {
+            Param::Float(value, min, max) => value >= min && value <= max,
+            Param::Int(value, min, max) => value >= min && value <= max,
+            Param::Bool(_) => true,
+            Param::Vector(v) => v[0] * v[0] + v[1] * v[1] + v[2] * v[2] <= 1.0,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(Error::InvalidValue)
+        }
+    }
+}
+
+#[derive(Debug)]
+/// A set of parameters for an audio effect, which can be loaded into an auxiliary effect slot.
+///
+/// Requires `ALC_EXT_EFX`.
+pub struct Effect<'a> {
+    context: &'a Context<'a>,
+    name: ALuint,
+    effect: EffectType,
+    delete: DeleteEffectsFn,
+    effecti: EffectiFn,
+    effectf: EffectfFn,
+    effectfv: EffectfvFn,
+}
+
+impl<'a> Effect<'a> {
+    /// Creates a new effect of the given type.
+    pub fn new(context: &'a Context<'a>, effect: EffectType) -> Result<Self, Error> {
+        let (gen, delete, effecti, effectf, effectfv) = unsafe {
+            (
+                context.extension_function::<GenEffectsFn>("ALC_EXT_EFX", "alGenEffects")?,
+                context.extension_function("ALC_EXT_EFX", "alDeleteEffects")?,
+                context.extension_function("ALC_EXT_EFX", "alEffecti")?,
+                context.extension_function("ALC_EXT_EFX", "alEffectf")?,
+                context.extension_function("ALC_EXT_EFX", "alEffectfv")?,
+            )
+        };
+        effect.visit(&mut |_, param| param.validate())?;
+        let mut name = 0;
+        context.call(|| unsafe { gen(1, &mut name) })?;
+        let mut result = Self {
+            context,
+            name,
+            effect,
+            delete,
+            effecti,
+            effectf,
+            effectfv,
+        };
+        result.upload(true).map(|()| result)
+    }
+
+    /// Returns the type and parameters of this effect.
+    pub fn effect_type(&self) -> &EffectType {
+        &self.effect
+    }
+
+    /// Replaces the type and parameters of this effect.
+    ///
+    /// Parameters are checked against their valid ranges before any are changed, returning
+    /// `Error::InvalidValue` if one is out of range. Slots holding this effect must have it
+    /// reloaded before the change is heard.
+    pub fn set(&mut self, effect: EffectType) -> Result<(), Error> {
+        effect.visit(&mut |_, param| param.validate())?;
+        let set_type = effect.to_al() != self.effect.to_al();
+        self.effect = effect;
+        self.upload(set_type)
+    }
+
+    pub(crate) fn name(&self) -> ALuint {
+        self.name
+    }
+
+    fn upload(&mut self, set_type: bool) -> Result<(), Error> {
+        let (name, effecti, effectf, effectfv) =
+            (self.name, self.effecti, self.effectf, self.effectfv);
+        let context = self.context;
+        if set_type {
+            let effect_type = self.effect.to_al();
+            context.call(|| unsafe { effecti(name, AL_EFFECT_TYPE, effect_type) })?;
+        }
+        self.effect.visit(&mut |param, value| {
+            context.call(|| unsafe {
+                match value {
+                    Param::Float(value, _, _) => effectf(name, param, value),
+                    Param::Int(value, _, _) => effecti(name, param, value),
+                    Param::Bool(value) => effecti(name, param, ALint::from(value)),
+                    Param::Vector(value) => effectfv(name, param, value.as_ptr()),
+                }
+            })
+        })
+    }
+}
+
+impl<'a> Drop for Effect<'a> {
+    fn drop(&mut self) {
+        let (name, delete) = (self.name, self.delete);
+        let _ = self.context.call(|| unsafe { delete(1, &name) });
+    }
+}
@@ -0,0 +1,131 @@
+// Copyright (c) 2018 FaultyRAM
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the
+// MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at
+// your option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use bindgen_openal_sys::{
+    ALCenum, ALenum, ALC_INVALID_CONTEXT, ALC_INVALID_DEVICE, ALC_INVALID_ENUM, ALC_INVALID_VALUE,
+    ALC_NO_ERROR, ALC_OUT_OF_MEMORY, AL_INVALID_ENUM, AL_INVALID_NAME, AL_INVALID_VALUE,
+    AL_NO_ERROR, AL_OUT_OF_MEMORY,
+};
+#[cfg(feature = "flac")]
+use claxon;
+use core::fmt::{self, Display, Formatter};
+use format::Format;
+#[cfg(feature = "vorbis")]
+use lewton::VorbisError;
+#[cfg(feature = "std")]
+use std::{error, io};
+#[cfg(feature = "mp3")]
+use symphonia_core::errors::Error as SymphoniaError;
+use wav::WavError;
+
+#[derive(Debug)]
+/// An error returned by an OpenAL operation.
+pub enum Error {
+    /// An object name was invalid.
+    InvalidName,
+    /// An enumeration value was invalid.
+    InvalidEnum,
+    /// A parameter value was invalid.
+    InvalidValue,
+    /// The requested operation is not valid in the current state.
+    InvalidOperation,
+    /// OpenAL ran out of memory.
+    OutOfMemory,
+    /// A device handle was invalid.
+    InvalidDevice,
+    /// A context handle was invalid.
+    InvalidContext,
+    /// The named extension is required but is not supported by the implementation.
+    ExtensionNotPresent(&'static str),
+    /// Audio data has a number of channels that cannot be played.
+    UnsupportedChannels(usize),
+    /// Audio data is in a format that OpenAL has no enumeration value for.
+    UnsupportedFormat(Format),
+    /// WAV data could not be parsed.
+    Wav(WavError),
+    #[cfg(feature = "vorbis")]
+    /// Ogg Vorbis data could not be decoded.
+    Vorbis(VorbisError),
+    #[cfg(feature = "flac")]
+    /// FLAC data could not be decoded.
+    Flac(claxon::Error),
+    #[cfg(feature = "mp3")]
+    /// MP3 data could not be decoded.
+    Mp3(SymphoniaError),
+    #[cfg(feature = "std")]
+    /// An I/O error occurred while reading audio data.
+    Io(io::Error),
+}
+
+impl Error {
+    /// Converts an `alGetError` result into an `Error`.
+    pub(crate) fn from_al(code: ALenum) -> Option<Self> {
+        match code {
+            AL_NO_ERROR => None,
+            AL_INVALID_NAME => Some(Error::InvalidName),
+            AL_INVALID_ENUM => Some(Error::InvalidEnum),
+            AL_INVALID_VALUE => Some(Error::InvalidValue),
+            AL_OUT_OF_MEMORY => Some(Error::OutOfMemory),
+            _ => Some(Error::InvalidOperation),
+        }
+    }
+
+    /// Converts an `alcGetError` result into an `Error`.
+    pub(crate) fn from_alc(code: ALCenum) -> Option<Self> {
+        match code {
+            ALC_NO_ERROR => None,
+            ALC_INVALID_DEVICE => Some(Error::InvalidDevice),
+            ALC_INVALID_CONTEXT => Some(Error::InvalidContext),
+            ALC_INVALID_ENUM => Some(Error::InvalidEnum),
+            ALC_INVALID_VALUE => Some(Error::InvalidValue),
+            ALC_OUT_OF_MEMORY => Some(Error::OutOfMemory),
+            _ => Some(Error::InvalidOperation),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidName => f.write_str("invalid object name"),
+            Error::InvalidEnum => f.write_str("invalid enumeration value"),
+            Error::InvalidValue => f.write_str("invalid parameter value"),
+            Error::InvalidOperation => f.write_str("invalid operation"),
+            Error::OutOfMemory => f.write_str("out of memory"),
+            Error::InvalidDevice => f.write_str("invalid device"),
+            Error::InvalidContext => f.write_str("invalid context"),
+            Error::ExtensionNotPresent(name) => write!(f, "extension {} is not present", name),
+            Error::UnsupportedChannels(channels) => {
+                write!(f, "unsupported channel count of {}", channels)
+            }
+            Error::UnsupportedFormat(format) => write!(
+                f,
+                "unsupported format of {:?} {:?} samples",
+                format.layout, format.sample_type
+            ),
+            Error::Wav(e) => e.fmt(f),
+            #[cfg(feature = "vorbis")]
+            Error::Vorbis(e) => e.fmt(f),
+            #[cfg(feature = "flac")]
+            Error::Flac(e) => e.fmt(f),
+            #[cfg(feature = "mp3")]
+            Error::Mp3(e) => e.fmt(f),
+            #[cfg(feature = "std")]
+            Error::Io(e) => e.fmt(f),
+        }
+    }
+}
+
+impl From<WavError> for Error {
+    fn from(e: WavError) -> Self {
+        Error::Wav(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for Error {}
@@ -0,0 +1,313 @@
+// Copyright (c) 2018 FaultyRAM
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the
+// MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at
+// your option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Extension definitions from OpenAL Soft's `alext.h` and `efx.h`.
+//!
+//! The Creative OpenAL SDK doesn't ship `alext.h`, and only declares EFX functions as pointer
+//! types, so these can't be generated by `bindgen-openal-sys` and are declared by hand instead.
+
+use bindgen_openal_sys::{
+    ALCdevice, ALCenum, ALCsizei, ALdouble, ALenum, ALfloat, ALint, ALsizei, ALuint, ALvoid,
+};
+
+pub(crate) const AL_FORMAT_MONO_FLOAT32: ALenum = 0x1_0010;
+pub(crate) const AL_FORMAT_STEREO_FLOAT32: ALenum = 0x1_0011;
+pub(crate) const AL_FORMAT_MONO_DOUBLE_EXT: ALenum = 0x1_0012;
+pub(crate) const AL_FORMAT_STEREO_DOUBLE_EXT: ALenum = 0x1_0013;
+pub(crate) const AL_FORMAT_MONO_MULAW_EXT: ALenum = 0x1_0014;
+pub(crate) const AL_FORMAT_STEREO_MULAW_EXT: ALenum = 0x1_0015;
+pub(crate) const AL_FORMAT_MONO_ALAW_EXT: ALenum = 0x1_0016;
+pub(crate) const AL_FORMAT_STEREO_ALAW_EXT: ALenum = 0x1_0017;
+pub(crate) const AL_FORMAT_QUAD8: ALenum = 0x1204;
+pub(crate) const AL_FORMAT_QUAD16: ALenum = 0x1205;
+pub(crate) const AL_FORMAT_QUAD32: ALenum = 0x1206;
+pub(crate) const AL_FORMAT_REAR8: ALenum = 0x1207;
+pub(crate) const AL_FORMAT_REAR16: ALenum = 0x1208;
+pub(crate) const AL_FORMAT_REAR32: ALenum = 0x1209;
+pub(crate) const AL_FORMAT_51CHN8: ALenum = 0x120A;
+pub(crate) const AL_FORMAT_51CHN16: ALenum = 0x120B;
+pub(crate) const AL_FORMAT_51CHN32: ALenum = 0x120C;
+pub(crate) const AL_FORMAT_61CHN8: ALenum = 0x120D;
+pub(crate) const AL_FORMAT_61CHN16: ALenum = 0x120E;
+pub(crate) const AL_FORMAT_61CHN32: ALenum = 0x120F;
+pub(crate) const AL_FORMAT_71CHN8: ALenum = 0x1210;
+pub(crate) const AL_FORMAT_71CHN16: ALenum = 0x1211;
+pub(crate) const AL_FORMAT_71CHN32: ALenum = 0x1212;
+pub(crate) const AL_FORMAT_QUAD_MULAW: ALenum = 0x1_0021;
+pub(crate) const AL_FORMAT_REAR_MULAW: ALenum = 0x1_0022;
+pub(crate) const AL_FORMAT_51CHN_MULAW: ALenum = 0x1_0023;
+pub(crate) const AL_FORMAT_61CHN_MULAW: ALenum = 0x1_0024;
+pub(crate) const AL_FORMAT_71CHN_MULAW: ALenum = 0x1_0025;
+pub(crate) const AL_FORMAT_BFORMAT2D_8: ALenum = 0x2_0021;
+pub(crate) const AL_FORMAT_BFORMAT2D_16: ALenum = 0x2_0022;
+pub(crate) const AL_FORMAT_BFORMAT2D_FLOAT32: ALenum = 0x2_0023;
+pub(crate) const AL_FORMAT_BFORMAT3D_8: ALenum = 0x2_0031;
+pub(crate) const AL_FORMAT_BFORMAT3D_16: ALenum = 0x2_0032;
+pub(crate) const AL_FORMAT_BFORMAT3D_FLOAT32: ALenum = 0x2_0033;
+pub(crate) const AL_FORMAT_BFORMAT2D_MULAW: ALenum = 0x1_0031;
+pub(crate) const AL_FORMAT_BFORMAT3D_MULAW: ALenum = 0x1_0032;
+pub(crate) const AL_AMBISONIC_LAYOUT_SOFT: ALenum = 0x1997;
+pub(crate) const AL_AMBISONIC_SCALING_SOFT: ALenum = 0x1998;
+pub(crate) const AL_FUMA_SOFT: ALint = 0x0000;
+pub(crate) const AL_ACN_SOFT: ALint = 0x0001;
+pub(crate) const AL_SN3D_SOFT: ALint = 0x0001;
+pub(crate) const AL_N3D_SOFT: ALint = 0x0002;
+pub(crate) const AL_UNPACK_AMBISONIC_ORDER_SOFT: ALenum = 0x199D;
+pub(crate) const AL_FORMAT_MONO_IMA4: ALenum = 0x1300;
+pub(crate) const AL_FORMAT_STEREO_IMA4: ALenum = 0x1301;
+pub(crate) const AL_FORMAT_MONO_MSADPCM_SOFT: ALenum = 0x1302;
+pub(crate) const AL_FORMAT_STEREO_MSADPCM_SOFT: ALenum = 0x1303;
+pub(crate) const AL_UNPACK_BLOCK_ALIGNMENT_SOFT: ALenum = 0x200C;
+pub(crate) const AL_LOOP_POINTS_SOFT: ALenum = 0x2015;
+pub(crate) const AL_SOURCE_DISTANCE_MODEL: ALenum = 0x0200;
+pub(crate) const AL_MAP_READ_BIT_SOFT: ALbitfieldSOFT = 0x0000_0001;
+pub(crate) const AL_MAP_WRITE_BIT_SOFT: ALbitfieldSOFT = 0x0000_0002;
+pub(crate) const AL_MAP_PERSISTENT_BIT_SOFT: ALbitfieldSOFT = 0x0000_0004;
+pub(crate) const AL_SAMPLE_OFFSET_LATENCY_SOFT: ALenum = 0x1200;
+pub(crate) const AL_SEC_OFFSET_LATENCY_SOFT: ALenum = 0x1201;
+pub(crate) const ALC_DEVICE_CLOCK_SOFT: ALCenum = 0x1600;
+pub(crate) const ALC_DEVICE_LATENCY_SOFT: ALCenum = 0x1601;
+pub(crate) const ALC_DEVICE_CLOCK_LATENCY_SOFT: ALCenum = 0x1602;
+
+pub(crate) const AL_EFFECT_TYPE: ALenum = 0x8001;
+pub(crate) const AL_EFFECT_REVERB: ALint = 0x0001;
+pub(crate) const AL_EFFECT_CHORUS: ALint = 0x0002;
+pub(crate) const AL_EFFECT_DISTORTION: ALint = 0x0003;
+pub(crate) const AL_EFFECT_ECHO: ALint = 0x0004;
+pub(crate) const AL_EFFECT_FLANGER: ALint = 0x0005;
+pub(crate) const AL_EFFECT_FREQUENCY_SHIFTER: ALint = 0x0006;
+pub(crate) const AL_EFFECT_VOCAL_MORPHER: ALint = 0x0007;
+pub(crate) const AL_EFFECT_PITCH_SHIFTER: ALint = 0x0008;
+pub(crate) const AL_EFFECT_RING_MODULATOR: ALint = 0x0009;
+pub(crate) const AL_EFFECT_AUTOWAH: ALint = 0x000A;
+pub(crate) const AL_EFFECT_COMPRESSOR: ALint = 0x000B;
+pub(crate) const AL_EFFECT_EQUALIZER: ALint = 0x000C;
+pub(crate) const AL_EFFECT_EAXREVERB: ALint = 0x8000;
+
+pub(crate) const AL_REVERB_DENSITY: ALenum = 0x0001;
+pub(crate) const AL_REVERB_DIFFUSION: ALenum = 0x0002;
+pub(crate) const AL_REVERB_GAIN: ALenum = 0x0003;
+pub(crate) const AL_REVERB_GAINHF: ALenum = 0x0004;
+pub(crate) const AL_REVERB_DECAY_TIME: ALenum = 0x0005;
+pub(crate) const AL_REVERB_DECAY_HFRATIO: ALenum = 0x0006;
+pub(crate) const AL_REVERB_REFLECTIONS_GAIN: ALenum = 0x0007;
+pub(crate) const AL_REVERB_REFLECTIONS_DELAY: ALenum = 0x0008;
+pub(crate) const AL_REVERB_LATE_REVERB_GAIN: ALenum = 0x0009;
+pub(crate) const AL_REVERB_LATE_REVERB_DELAY: ALenum = 0x000A;
+pub(crate) const AL_REVERB_AIR_ABSORPTION_GAINHF: ALenum = 0x000B;
+pub(crate) const AL_REVERB_ROOM_ROLLOFF_FACTOR: ALenum = 0x000C;
+pub(crate) const AL_REVERB_DECAY_HFLIMIT: ALenum = 0x000D;
+
+pub(crate) const AL_EAXREVERB_DENSITY: ALenum = 0x0001;
+pub(crate) const AL_EAXREVERB_DIFFUSION: ALenum = 0x0002;
+pub(crate) const AL_EAXREVERB_GAIN: ALenum = 0x0003;
+pub(crate) const AL_EAXREVERB_GAINHF: ALenum = 0x0004;
+pub(crate) const AL_EAXREVERB_GAINLF: ALenum = 0x0005;
+pub(crate) const AL_EAXREVERB_DECAY_TIME: ALenum = 0x0006;
+pub(crate) const AL_EAXREVERB_DECAY_HFRATIO: ALenum = 0x0007;
+pub(crate) const AL_EAXREVERB_DECAY_LFRATIO: ALenum = 0x0008;
+pub(crate) const AL_EAXREVERB_REFLECTIONS_GAIN: ALenum = 0x0009;
+pub(crate) const AL_EAXREVERB_REFLECTIONS_DELAY: ALenum = 0x000A;
+pub(crate) const AL_EAXREVERB_REFLECTIONS_PAN: ALenum = 0x000B;
+pub(crate) const AL_EAXREVERB_LATE_REVERB_GAIN: ALenum = 0x000C;
+pub(crate) const AL_EAXREVERB_LATE_REVERB_DELAY: ALenum = 0x000D;
+pub(crate) const AL_EAXREVERB_LATE_REVERB_PAN: ALenum = 0x000E;
+pub(crate) const AL_EAXREVERB_ECHO_TIME: ALenum = 0x000F;
+pub(crate) const AL_EAXREVERB_ECHO_DEPTH: ALenum = 0x0010;
+pub(crate) const AL_EAXREVERB_MODULATION_TIME: ALenum = 0x0011;
+pub(crate) const AL_EAXREVERB_MODULATION_DEPTH: ALenum = 0x0012;
+pub(crate) const AL_EAXREVERB_AIR_ABSORPTION_GAINHF: ALenum = 0x0013;
+pub(crate) const AL_EAXREVERB_HFREFERENCE: ALenum = 0x0014;
+pub(crate) const AL_EAXREVERB_LFREFERENCE: ALenum = 0x0015;
+pub(crate) const AL_EAXREVERB_ROOM_ROLLOFF_FACTOR: ALenum = 0x0016;
+pub(crate) const AL_EAXREVERB_DECAY_HFLIMIT: ALenum = 0x0017;
+
+pub(crate) const AL_CHORUS_WAVEFORM: ALenum = 0x0001;
+pub(crate) const AL_CHORUS_PHASE: ALenum = 0x0002;
+pub(crate) const AL_CHORUS_RATE: ALenum = 0x0003;
+pub(crate) const AL_CHORUS_DEPTH: ALenum = 0x0004;
+pub(crate) const AL_CHORUS_FEEDBACK: ALenum = 0x0005;
+pub(crate) const AL_CHORUS_DELAY: ALenum = 0x0006;
+
+pub(crate) const AL_DISTORTION_EDGE: ALenum = 0x0001;
+pub(crate) const AL_DISTORTION_GAIN: ALenum = 0x0002;
+pub(crate) const AL_DISTORTION_LOWPASS_CUTOFF: ALenum = 0x0003;
+pub(crate) const AL_DISTORTION_EQCENTER: ALenum = 0x0004;
+pub(crate) const AL_DISTORTION_EQBANDWIDTH: ALenum = 0x0005;
+
+pub(crate) const AL_ECHO_DELAY: ALenum = 0x0001;
+pub(crate) const AL_ECHO_LRDELAY: ALenum = 0x0002;
+pub(crate) const AL_ECHO_DAMPING: ALenum = 0x0003;
+pub(crate) const AL_ECHO_FEEDBACK: ALenum = 0x0004;
+pub(crate) const AL_ECHO_SPREAD: ALenum = 0x0005;
+
+pub(crate) const AL_FLANGER_WAVEFORM: ALenum = 0x0001;
+pub(crate) const AL_FLANGER_PHASE: ALenum = 0x0002;
+pub(crate) const AL_FLANGER_RATE: ALenum = 0x0003;
+pub(crate) const AL_FLANGER_DEPTH: ALenum = 0x0004;
+pub(crate) const AL_FLANGER_FEEDBACK: ALenum = 0x0005;
+pub(crate) const AL_FLANGER_DELAY: ALenum = 0x0006;
+
+pub(crate) const AL_FREQUENCY_SHIFTER_FREQUENCY: ALenum = 0x0001;
+pub(crate) const AL_FREQUENCY_SHIFTER_LEFT_DIRECTION: ALenum = 0x0002;
+pub(crate) const AL_FREQUENCY_SHIFTER_RIGHT_DIRECTION: ALenum = 0x0003;
+
+pub(crate) const AL_VOCAL_MORPHER_PHONEMEA: ALenum = 0x0001;
+pub(crate) const AL_VOCAL_MORPHER_PHONEMEA_COARSE_TUNING: ALenum = 0x0002;
+pub(crate) const AL_VOCAL_MORPHER_PHONEMEB: ALenum = 0x0003;
+pub(crate) const AL_VOCAL_MORPHER_PHONEMEB_COARSE_TUNING: ALenum = 0x0004;
+pub(crate) const AL_VOCAL_MORPHER_WAVEFORM: ALenum = 0x0005;
+pub(crate) const AL_VOCAL_MORPHER_RATE: ALenum = 0x0006;
+
+pub(crate) const AL_PITCH_SHIFTER_COARSE_TUNE: ALenum = 0x0001;
+pub(crate) const AL_PITCH_SHIFTER_FINE_TUNE: ALenum = 0x0002;
+
+pub(crate) const AL_RING_MODULATOR_FREQUENCY: ALenum = 0x0001;
+pub(crate) const AL_RING_MODULATOR_HIGHPASS_CUTOFF: ALenum = 0x0002;
+pub(crate) const AL_RING_MODULATOR_WAVEFORM: ALenum = 0x0003;
+
+pub(crate) const AL_AUTOWAH_ATTACK_TIME: ALenum = 0x0001;
+pub(crate) const AL_AUTOWAH_RELEASE_TIME: ALenum = 0x0002;
+pub(crate) const AL_AUTOWAH_RESONANCE: ALenum = 0x0003;
+pub(crate) const AL_AUTOWAH_PEAK_GAIN: ALenum = 0x0004;
+
+pub(crate) const AL_COMPRESSOR_ONOFF: ALenum = 0x0001;
+
+pub(crate) const AL_EQUALIZER_LOW_GAIN: ALenum = 0x0001;
+pub(crate) const AL_EQUALIZER_LOW_CUTOFF: ALenum = 0x0002;
+pub(crate) const AL_EQUALIZER_MID1_GAIN: ALenum = 0x0003;
+pub(crate) const AL_EQUALIZER_MID1_CENTER: ALenum = 0x0004;
+pub(crate) const AL_EQUALIZER_MID1_WIDTH: ALenum = 0x0005;
+pub(crate) const AL_EQUALIZER_MID2_GAIN: ALenum = 0x0006;
+pub(crate) const AL_EQUALIZER_MID2_CENTER: ALenum = 0x0007;
+pub(crate) const AL_EQUALIZER_MID2_WIDTH: ALenum = 0x0008;
+pub(crate) const AL_EQUALIZER_HIGH_GAIN: ALenum = 0x0009;
+pub(crate) const AL_EQUALIZER_HIGH_CUTOFF: ALenum = 0x000A;
+
+pub(crate) const AL_FILTER_TYPE: ALenum = 0x8001;
+pub(crate) const AL_FILTER_LOWPASS: ALint = 0x0001;
+pub(crate) const AL_FILTER_HIGHPASS: ALint = 0x0002;
+pub(crate) const AL_FILTER_BANDPASS: ALint = 0x0003;
+pub(crate) const AL_LOWPASS_GAIN: ALenum = 0x0001;
+pub(crate) const AL_LOWPASS_GAINHF: ALenum = 0x0002;
+pub(crate) const AL_HIGHPASS_GAIN: ALenum = 0x0001;
+pub(crate) const AL_HIGHPASS_GAINLF: ALenum = 0x0002;
+pub(crate) const AL_BANDPASS_GAIN: ALenum = 0x0001;
+pub(crate) const AL_BANDPASS_GAINLF: ALenum = 0x0002;
+pub(crate) const AL_BANDPASS_GAINHF: ALenum = 0x0003;
+pub(crate) const AL_DIRECT_FILTER: ALenum = 0x2_0005;
+pub(crate) const AL_AUXILIARY_SEND_FILTER: ALenum = 0x2_0006;
+pub(crate) const AL_EFFECTSLOT_EFFECT: ALenum = 0x0001;
+pub(crate) const AL_EFFECTSLOT_GAIN: ALenum = 0x0002;
+pub(crate) const AL_EFFECTSLOT_AUXILIARY_SEND_AUTO: ALenum = 0x0003;
+pub(crate) const ALC_MAX_AUXILIARY_SENDS: ALCenum = 0x2_0003;
+
+/// `ALbitfieldSOFT`.
+pub(crate) type ALbitfieldSOFT = ALuint;
+/// `ALint64SOFT`.
+pub(crate) type ALint64SOFT = i64;
+/// `ALCint64SOFT`.
+pub(crate) type ALCint64SOFT = i64;
+
+#[cfg(feature = "std")]
+/// `ALBUFFERCALLBACKTYPESOFT`.
+pub(crate) type BufferCallbackType = unsafe extern "C" fn(
+    userptr: *mut ALvoid,
+    sampledata: *mut ALvoid,
+    numbytes: ALsizei,
+) -> ALsizei;
+#[cfg(feature = "std")]
+/// `LPALBUFFERCALLBACKSOFT`.
+pub(crate) type BufferCallbackFn = unsafe extern "C" fn(
+    buffer: ALuint,
+    format: ALenum,
+    freq: ALsizei,
+    callback: BufferCallbackType,
+    userptr: *mut ALvoid,
+);
+/// `LPALBUFFERSTORAGESOFT`.
+pub(crate) type BufferStorageFn = unsafe extern "C" fn(
+    buffer: ALuint,
+    format: ALenum,
+    data: *const ALvoid,
+    size: ALsizei,
+    freq: ALsizei,
+    flags: ALbitfieldSOFT,
+);
+/// `LPALMAPBUFFERSOFT`.
+pub(crate) type MapBufferFn = unsafe extern "C" fn(
+    buffer: ALuint,
+    offset: ALsizei,
+    length: ALsizei,
+    access: ALbitfieldSOFT,
+) -> *mut ALvoid;
+/// `LPALUNMAPBUFFERSOFT`.
+pub(crate) type UnmapBufferFn = unsafe extern "C" fn(buffer: ALuint);
+/// `LPALFLUSHMAPPEDBUFFERSOFT`.
+pub(crate) type FlushMappedBufferFn =
+    unsafe extern "C" fn(buffer: ALuint, offset: ALsizei, length: ALsizei);
+/// `PFNALBUFFERSUBDATASOFTPROC`.
+pub(crate) type BufferSubDataFn = unsafe extern "C" fn(
+    buffer: ALuint,
+    format: ALenum,
+    data: *const ALvoid,
+    offset: ALsizei,
+    length: ALsizei,
+);
+/// `LPALGETSOURCEI64VSOFT`.
+pub(crate) type GetSourcei64vFn =
+    unsafe extern "C" fn(source: ALuint, param: ALenum, values: *mut ALint64SOFT);
+/// `LPALGETSOURCEDVSOFT`.
+pub(crate) type GetSourcedvFn =
+    unsafe extern "C" fn(source: ALuint, param: ALenum, values: *mut ALdouble);
+/// `LPALCGETINTEGER64VSOFT`.
+pub(crate) type GetInteger64vFn = unsafe extern "C" fn(
+    device: *mut ALCdevice,
+    pname: ALCenum,
+    size: ALCsizei,
+    values: *mut ALCint64SOFT,
+);
+/// `LPALSOURCEPLAYATTIMESOFT`.
+pub(crate) type SourcePlayAtTimeFn = unsafe extern "C" fn(source: ALuint, start_time: ALint64SOFT);
+/// `LPALSOURCEPLAYATTIMEVSOFT`.
+pub(crate) type SourcePlayAtTimevFn =
+    unsafe extern "C" fn(n: ALsizei, sources: *const ALuint, start_time: ALint64SOFT);
+/// `LPALDEFERUPDATESSOFT`.
+pub(crate) type DeferUpdatesFn = unsafe extern "C" fn();
+/// `LPALPROCESSUPDATESSOFT`.
+pub(crate) type ProcessUpdatesFn = unsafe extern "C" fn();
+/// `LPALGENEFFECTS`.
+pub(crate) type GenEffectsFn = unsafe extern "C" fn(n: ALsizei, effects: *mut ALuint);
+/// `LPALDELETEEFFECTS`.
+pub(crate) type DeleteEffectsFn = unsafe extern "C" fn(n: ALsizei, effects: *const ALuint);
+/// `LPALEFFECTI`.
+pub(crate) type EffectiFn = unsafe extern "C" fn(effect: ALuint, param: ALenum, value: ALint);
+/// `LPALEFFECTF`.
+pub(crate) type EffectfFn = unsafe extern "C" fn(effect: ALuint, param: ALenum, value: ALfloat);
+/// `LPALEFFECTFV`.
+pub(crate) type EffectfvFn =
+    unsafe extern "C" fn(effect: ALuint, param: ALenum, values: *const ALfloat);
+/// `LPALGENFILTERS`.
+pub(crate) type GenFiltersFn = unsafe extern "C" fn(n: ALsizei, filters: *mut ALuint);
+/// `LPALDELETEFILTERS`.
+pub(crate) type DeleteFiltersFn = unsafe extern "C" fn(n: ALsizei, filters: *const ALuint);
+/// `LPALFILTERI`.
+pub(crate) type FilteriFn = unsafe extern "C" fn(filter: ALuint, param: ALenum, value: ALint);
+/// `LPALFILTERF`.
+pub(crate) type FilterfFn = unsafe extern "C" fn(filter: ALuint, param: ALenum, value: ALfloat);
+/// `LPALGENAUXILIARYEFFECTSLOTS`.
+pub(crate) type GenAuxiliaryEffectSlotsFn = unsafe extern "C" fn(n: ALsizei, slots: *mut ALuint);
+/// `LPALDELETEAUXILIARYEFFECTSLOTS`.
+pub(crate) type DeleteAuxiliaryEffectSlotsFn =
+    unsafe extern "C" fn(n: ALsizei, slots: *const ALuint);
+/// `LPALAUXILIARYEFFECTSLOTI`.
+pub(crate) type AuxiliaryEffectSlotiFn =
+    unsafe extern "C" fn(slot: ALuint, param: ALenum, value: ALint);
+/// `LPALAUXILIARYEFFECTSLOTF`.
+pub(crate) type AuxiliaryEffectSlotfFn =
+    unsafe extern "C" fn(slot: ALuint, param: ALenum, value: ALfloat);
@@ -0,0 +1,200 @@
+// Copyright (c) 2018 FaultyRAM
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the
+// MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at
+// your option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use bindgen_openal_sys::{ALenum, ALint, ALuint};
+use context::Context;
+use core::cell::Cell;
+use error::Error;
+use ext::{
+    DeleteFiltersFn, FilterfFn, FilteriFn, GenFiltersFn, AL_BANDPASS_GAIN, AL_BANDPASS_GAINHF,
+    AL_BANDPASS_GAINLF, AL_FILTER_BANDPASS, AL_FILTER_HIGHPASS, AL_FILTER_LOWPASS, AL_FILTER_TYPE,
+    AL_HIGHPASS_GAIN, AL_HIGHPASS_GAINLF, AL_LOWPASS_GAIN, AL_LOWPASS_GAINHF,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The type of a filter, along with its parameters.
+pub enum FilterType {
+    /// Attenuates high frequencies.
+    LowPass(LowPass),
+    /// Attenuates low frequencies.
+    HighPass(HighPass),
+    /// Attenuates both high and low frequencies.
+    BandPass(BandPass),
+}
+
+impl FilterType {
+    fn to_al(self) -> ALint {
+        match self {
+            FilterType::LowPass(_) => AL_FILTER_LOWPASS,
+            FilterType::HighPass(_) => AL_FILTER_HIGHPASS,
+            FilterType::BandPass(_) => AL_FILTER_BANDPASS,
+        }
+    }
+
+    /// Passes each parameter to `f`.
+    fn visit(&self, f: &mut dyn FnMut(ALenum, f32) -> Result<(), Error>) -> Result<(), Error> {
+        match *self {
+            FilterType::LowPass(params) => {
+                f(AL_LOWPASS_GAIN, params.gain)?;
+                f(AL_LOWPASS_GAINHF, params.gain_hf)
+            }
+            FilterType::HighPass(params) => {
+                f(AL_HIGHPASS_GAIN, params.gain)?;
+                f(AL_HIGHPASS_GAINLF, params.gain_lf)
+            }
+            FilterType::BandPass(params) => {
+                f(AL_BANDPASS_GAIN, params.gain)?;
+                f(AL_BANDPASS_GAINLF, params.gain_lf)?;
+                f(AL_BANDPASS_GAINHF, params.gain_hf)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The parameters of a low-pass filter.
+pub struct LowPass {
+    /// The overall level, from 0 to 1.
+    pub gain: f32,
+    /// The level of high frequencies, from 0 to 1.
+    pub gain_hf: f32,
+}
+
+impl Default for LowPass {
+    fn default() -> Self {
+        Self {
+            gain: 1.0,
+            gain_hf: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The parameters of a high-pass filter.
+pub struct HighPass {
+    /// The overall level, from 0 to 1.
+    pub gain: f32,
+    /// The level of low frequencies, from 0 to 1.
+    pub gain_lf: f32,
+}
+
+impl Default for HighPass {
+    fn default() -> Self {
+        Self {
+            gain: 1.0,
+            gain_lf: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The parameters of a band-pass filter.
+pub struct BandPass {
+    /// The overall level, from 0 to 1.
+    pub gain: f32,
+    /// The level of low frequencies, from 0 to 1.
+    pub gain_lf: f32,
+    /// The level of high frequencies, from 0 to 1.
+    pub gain_hf: f32,
+}
+
+impl Default for BandPass {
+    fn default() -> Self {
+        Self {
+            gain: 1.0,
+            gain_lf: 1.0,
+            gain_hf: 1.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+/// A filter that can be applied to the direct path or auxiliary sends of a source.
+///
+/// Requires `ALC_EXT_EFX`.
+pub struct Filter<'a> {
+    context: &'a Context<'a>,
+    name: ALuint,
+    filter: Cell<FilterType>,
+    delete: DeleteFiltersFn,
+    filteri: FilteriFn,
+    filterf: FilterfFn,
+}
+
+impl<'a> Filter<'a> {
+    /// Creates a new filter of the given type.
+    pub fn new(context: &'a Context<'a>, filter: FilterType) -> Result<Self, Error> {
+        let (gen, delete, filteri, filterf) = unsafe {
+            (
+                context.extension_function::<GenFiltersFn>("ALC_EXT_EFX", "alGenFilters")?,
+                context.extension_function("ALC_EXT_EFX", "alDeleteFilters")?,
+                context.extension_function("ALC_EXT_EFX", "alFilteri")?,
+                context.extension_function("ALC_EXT_EFX", "alFilterf")?,
+            )
+        };
+        filter.visit(&mut validate)?;
+        let mut name = 0;
+        context.call(|| unsafe { gen(1, &mut name) })?;
+        let result = Self {
+            context,
+            name,
+            filter: Cell::new(filter),
+            delete,
+            filteri,
+            filterf,
+        };
+        result.upload(true).map(|()| result)
+    }
+
+    /// Returns the type and parameters of this filter.
+    pub fn filter_type(&self) -> FilterType {
+        self.filter.get()
+    }
+
+    /// Replaces the type and parameters of this filter.
+    ///
+    /// Parameters are checked against their valid ranges before any are changed, returning
+    /// `Error::InvalidValue` if one is out of range. This can be called while the filter is
+    /// attached to sources, but they must have it reattached before the change is heard.
+    pub fn set(&self, filter: FilterType) -> Result<(), Error> {
+        filter.visit(&mut validate)?;
+        let set_type = filter.to_al() != self.filter.get().to_al();
+        self.filter.set(filter);
+        self.upload(set_type)
+    }
+
+    pub(crate) fn name(&self) -> ALuint {
+        self.name
+    }
+
+    fn upload(&self, set_type: bool) -> Result<(), Error> {
+        let (name, filteri, filterf) = (self.name, self.filteri, self.filterf);
+        let (context, filter) = (self.context, self.filter.get());
+        if set_type {
+            let filter_type = filter.to_al();
+            context.call(|| unsafe { filteri(name, AL_FILTER_TYPE, filter_type) })?;
+        }
+        filter.visit(&mut |param, value| context.call(|| unsafe { filterf(name, param, value) }))
+    }
+}
+
+impl<'a> Drop for Filter<'a> {
+    fn drop(&mut self) {
+        let (name, delete) = (self.name, self.delete);
+        let _ = self.context.call(|| unsafe { delete(1, &name) });
+    }
+}
+
+/// Checks that a filter gain is between 0 and 1.
+fn validate(_: ALenum, gain: f32) -> Result<(), Error> {
+    if (0.0..=1.0).contains(&gain) {
+        Ok(())
+    } else {
+        Err(Error::InvalidValue)
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright (c) 2018 FaultyRAM
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the
+// MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at
+// your option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use claxon::FlacReader;
+use core::fmt::{self, Debug, Formatter};
+use core::mem;
+use error::Error;
+use format::{ChannelLayout, Format, SampleType};
+use pcm::PcmQueue;
+use std::io::Read;
+use std::vec::Vec;
+use stream::Decoder;
+
+/// A decoder for FLAC audio data.
+///
+/// FLAC streams with 1, 2, 4, 6, 7 or 8 channels are supported, and are decoded to 16-bit
+/// samples. Streams with more than 16 bits per sample are truncated.
+pub struct FlacDecoder<R: Read> {
+    reader: FlacReader<R>,
+    format: Format,
+    frequency: u32,
+    bits: u32,
+    block: Vec<i32>,
+    queue: PcmQueue,
+}
+
+impl<R: Read> FlacDecoder<R> {
+    /// Reads the FLAC headers from `reader` and creates a decoder for the stream that follows.
+    pub fn new(reader: R) -> Result<Self, Error> {
+        let reader = FlacReader::new(reader).map_err(Error::Flac)?;
+        let info = reader.streaminfo();
+        let channels = info.channels as usize;
+        let layout =
+            ChannelLayout::from_channels(channels).ok_or(Error::UnsupportedChannels(channels))?;
+        Ok(Self {
+            reader,
+            format: Format::new(layout, SampleType::I16),
+            frequency: info.sample_rate,
+            bits: info.bits_per_sample,
+            block: Vec::new(),
+            queue: PcmQueue::default(),
+        })
+    }
+}
+
+impl<R: Read> Debug for FlacDecoder<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlacDecoder")
+            .field("format", &self.format)
+            .field("frequency", &self.frequency)
+            .field("bits", &self.bits)
+            .finish()
+    }
+}
+
+impl<R: Read> Decoder for FlacDecoder<R> {
+    fn format(&self) -> Format {
+        self.format
+    }
+
+    fn frequency(&self) -> u32 {
+        self.frequency
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        let reader = &mut self.reader;
+        let block = &mut self.block;
+        let bits = self.bits;
+        self.queue
+            .read(buf, self.format.layout.channels(), |samples| {
+                let buffer = mem::take(block);
+                match reader
+                    .blocks()
+                    .read_next_or_eof(buffer)
+                    .map_err(Error::Flac)?
+                {
+                    Some(decoded) => {
+                        for i in 0..decoded.duration() {
+                            for channel in 0..decoded.channels() {
+                                samples.push(to_i16(decoded.sample(channel, i), bits));
+                            }
+                        }
+                        *block = decoded.into_buffer();
+                        Ok(true)
+                    }
+                    None => Ok(false),
+                }
+            })
+    }
+}
+
+/// Scales a sample with the given bit depth to 16 bits.
+fn to_i16(sample: i32, bits: u32) -> i16 {
+    if bits > 16 {
+        (sample >> (bits - 16)) as i16
+    } else {
+        (sample << (16 - bits)) as i16
+    }
+}
@@ -0,0 +1,453 @@
+// Copyright (c) 2018 FaultyRAM
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the
+// MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at
+// your option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use bindgen_openal_sys::{
+    ALenum, ALint, AL_FORMAT_MONO16, AL_FORMAT_MONO8, AL_FORMAT_STEREO16, AL_FORMAT_STEREO8,
+};
+#[cfg(feature = "std")]
+use convert::{Dither, OutputSample};
+use ext::{
+    AL_ACN_SOFT, AL_FORMAT_51CHN16, AL_FORMAT_51CHN32, AL_FORMAT_51CHN8, AL_FORMAT_51CHN_MULAW,
+    AL_FORMAT_61CHN16, AL_FORMAT_61CHN32, AL_FORMAT_61CHN8, AL_FORMAT_61CHN_MULAW,
+    AL_FORMAT_71CHN16, AL_FORMAT_71CHN32, AL_FORMAT_71CHN8, AL_FORMAT_71CHN_MULAW,
+    AL_FORMAT_BFORMAT2D_16, AL_FORMAT_BFORMAT2D_8, AL_FORMAT_BFORMAT2D_FLOAT32,
+    AL_FORMAT_BFORMAT2D_MULAW, AL_FORMAT_BFORMAT3D_16, AL_FORMAT_BFORMAT3D_8,
+    AL_FORMAT_BFORMAT3D_FLOAT32, AL_FORMAT_BFORMAT3D_MULAW, AL_FORMAT_MONO_ALAW_EXT,
+    AL_FORMAT_MONO_DOUBLE_EXT, AL_FORMAT_MONO_FLOAT32, AL_FORMAT_MONO_IMA4,
+    AL_FORMAT_MONO_MSADPCM_SOFT, AL_FORMAT_MONO_MULAW_EXT, AL_FORMAT_QUAD16, AL_FORMAT_QUAD32,
+    AL_FORMAT_QUAD8, AL_FORMAT_QUAD_MULAW, AL_FORMAT_REAR16, AL_FORMAT_REAR32, AL_FORMAT_REAR8,
+    AL_FORMAT_REAR_MULAW, AL_FORMAT_STEREO_ALAW_EXT, AL_FORMAT_STEREO_DOUBLE_EXT,
+    AL_FORMAT_STEREO_FLOAT32, AL_FORMAT_STEREO_IMA4, AL_FORMAT_STEREO_MSADPCM_SOFT,
+    AL_FORMAT_STEREO_MULAW_EXT, AL_FUMA_SOFT, AL_N3D_SOFT, AL_SN3D_SOFT,
+};
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+/// The arrangement of channels within a frame of audio data.
+pub enum ChannelLayout {
+    /// A single channel.
+    Mono,
+    /// Two channels, left then right.
+    Stereo,
+    /// Two channels, rear left then rear right.
+    ///
+    /// Requires `AL_EXT_MCFORMATS`.
+    Rear,
+    /// Four channels: front left, front right, rear left, rear right.
+    ///
+    /// Requires `AL_EXT_MCFORMATS`.
+    Quad,
+    /// 5.1 surround: front left, front right, front center, LFE, surround left, surround right.
+    ///
+    /// Requires `AL_EXT_MCFORMATS`.
+    Surround51,
+    /// 6.1 surround: front left, front right, front center, LFE, rear center, side left, side
+    /// right.
+    ///
+    /// Requires `AL_EXT_MCFORMATS`.
+    Surround61,
+    /// 7.1 surround: front left, front right, front center, LFE, rear left, rear right, side left,
+    /// side right.
+    ///
+    /// Requires `AL_EXT_MCFORMATS`.
+    Surround71,
+    /// Horizontal-only ambisonics of the given order, which must be at least 1.
+    ///
+    /// Requires `AL_EXT_BFORMAT`, and `AL_SOFT_bformat_hoa` for orders above 1. Channel ordering
+    /// and normalization default to FuMa, and can be changed with `Buffer::set_ambisonic_layout`
+    /// and `Buffer::set_ambisonic_scaling`.
+    BFormat2D(u8),
+    /// Full-sphere ambisonics of the given order, which must be at least 1.
+    ///
+    /// Requires `AL_EXT_BFORMAT`, and `AL_SOFT_bformat_hoa` for orders above 1. Channel ordering
+    /// and normalization default to FuMa, and can be changed with `Buffer::set_ambisonic_layout`
+    /// and `Buffer::set_ambisonic_scaling`.
+    BFormat3D(u8),
+}
+
+impl ChannelLayout {
+    /// Returns the number of channels in this layout.
+    pub fn channels(self) -> usize {
+        match self {
+            ChannelLayout::Mono => 1,
+            ChannelLayout::Stereo | ChannelLayout::Rear => 2,
+            ChannelLayout::Quad => 4,
+            ChannelLayout::Surround51 => 6,
+            ChannelLayout::Surround61 => 7,
+            ChannelLayout::Surround71 => 8,
+            ChannelLayout::BFormat2D(order) => usize::from(order) * 2 + 1,
+            ChannelLayout::BFormat3D(order) => (usize::from(order) + 1) * (usize::from(order) + 1),
+        }
+    }
+
+    /// Returns the ambisonic order of this layout, or `None` if it is not a B-Format layout.
+    pub fn ambisonic_order(self) -> Option<u8> {
+        match self {
+            ChannelLayout::BFormat2D(order) | ChannelLayout::BFormat3D(order) => Some(order),
+            _ => None,
+        }
+    }
+
+    /// Returns the layout with the given number of channels, if there is one.
+    ///
+    /// Two channels are assumed to be `Stereo` rather than `Rear`, and B-Format layouts are never
+    /// returned.
+    pub fn from_channels(channels: usize) -> Option<Self> {
+        match channels {
+            1 => Some(ChannelLayout::Mono),
+            2 => Some(ChannelLayout::Stereo),
+            4 => Some(ChannelLayout::Quad),
+            6 => Some(ChannelLayout::Surround51),
+            7 => Some(ChannelLayout::Surround61),
+            8 => Some(ChannelLayout::Surround71),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+/// The encoding of individual samples within a frame of audio data.
+pub enum SampleType {
+    /// Unsigned 8-bit PCM.
+    U8,
+    /// Signed 16-bit PCM.
+    I16,
+    /// 32-bit floating-point PCM. Requires `AL_EXT_FLOAT32`.
+    F32,
+    /// 64-bit floating-point PCM. Requires `AL_EXT_DOUBLE`, and is only available in mono or
+    /// stereo.
+    F64,
+    /// 8-bit G.711 mu-law. Requires `AL_EXT_MULAW`.
+    MuLaw,
+    /// 8-bit G.711 A-law. Requires `AL_EXT_ALAW`, and is only available in mono or stereo.
+    ALaw,
+    /// 4-bit IMA ADPCM, in blocks of the given number of frames, as found in WAV files.
+    ///
+    /// The block length must be one more than a multiple of 8. Requires `AL_EXT_IMA4`, and
+    /// `AL_SOFT_block_alignment` for block lengths other than 65. Only available in mono or stereo.
+    Ima4(u16),
+    /// 4-bit Microsoft ADPCM with the standard coefficients, in blocks of the given number of
+    /// frames.
+    ///
+    /// The block length must be an even number of at least 2. Requires `AL_SOFT_MSADPCM`, and
+    /// `AL_SOFT_block_alignment` for block lengths other than 64. Only available in mono or stereo.
+    MsAdpcm(u16),
+}
+
+impl SampleType {
+    /// Returns the size of a single sample, in bytes, or `None` if samples are compressed in
+    /// blocks.
+    pub fn size(self) -> Option<usize> {
+        match self {
+            SampleType::U8 | SampleType::MuLaw | SampleType::ALaw => Some(1),
+            SampleType::I16 => Some(2),
+            SampleType::F32 => Some(4),
+            SampleType::F64 => Some(8),
+            SampleType::Ima4(_) | SampleType::MsAdpcm(_) => None,
+        }
+    }
+
+    /// Returns the number of frames in each block of this sample type.
+    ///
+    /// Uncompressed sample types have one frame per block.
+    pub fn block_frames(self) -> usize {
+        match self {
+            SampleType::Ima4(frames) | SampleType::MsAdpcm(frames) => usize::from(frames),
+            _ => 1,
+        }
+    }
+
+    /// Returns the value of `AL_UNPACK_BLOCK_ALIGNMENT_SOFT` needed to upload this sample type.
+    ///
+    /// This is zero, meaning the implementation's default, unless a non-default ADPCM block length
+    /// is in use.
+    pub(crate) fn unpack_alignment(self) -> u16 {
+        match self {
+            SampleType::Ima4(65) | SampleType::MsAdpcm(64) => 0,
+            SampleType::Ima4(frames) | SampleType::MsAdpcm(frames) => frames,
+            _ => 0,
+        }
+    }
+
+    /// Returns whether the block length of an ADPCM sample type is valid.
+    fn is_valid(self) -> bool {
+        match self {
+            SampleType::Ima4(frames) => frames % 8 == 1,
+            SampleType::MsAdpcm(frames) => frames >= 2 && frames % 2 == 0,
+            _ => true,
+        }
+    }
+
+    /// Returns the AL extension required to use this sample type, if any.
+    pub(crate) fn extension(self) -> Option<&'static str> {
+        match self {
+            SampleType::U8 | SampleType::I16 => None,
+            SampleType::F32 => Some("AL_EXT_FLOAT32"),
+            SampleType::F64 => Some("AL_EXT_DOUBLE"),
+            SampleType::MuLaw => Some("AL_EXT_MULAW"),
+            SampleType::ALaw => Some("AL_EXT_ALAW"),
+            SampleType::Ima4(_) => Some("AL_EXT_IMA4"),
+            SampleType::MsAdpcm(_) => Some("AL_SOFT_MSADPCM"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+/// The format of audio data, consisting of a channel layout and a sample type.
+pub struct Format {
+    /// The arrangement of channels within each frame.
+    pub layout: ChannelLayout,
+    /// The encoding of each sample.
+    pub sample_type: SampleType,
+}
+
+impl Format {
+    /// Creates a new `Format` from a channel layout and a sample type.
+    pub fn new(layout: ChannelLayout, sample_type: SampleType) -> Self {
+        Self {
+            layout,
+            sample_type,
+        }
+    }
+
+    /// Returns the size of a single block, in bytes.
+    ///
+    /// For uncompressed sample types this is the size of one frame (one sample for each channel).
+    /// Audio data must always consist of whole blocks.
+    pub fn block_size(self) -> usize {
+        let channels = self.layout.channels();
+        match self.sample_type {
+            SampleType::Ima4(frames) => channels * (4 + usize::from(frames).saturating_sub(1) / 2),
+            SampleType::MsAdpcm(frames) => {
+                channels * (7 + usize::from(frames).saturating_sub(2) / 2)
+            }
+            sample_type => channels * sample_type.size().unwrap_or(0),
+        }
+    }
+
+    /// Returns the AL extensions required to use this format, if any.
+    pub(crate) fn extensions(self) -> [Option<&'static str>; 3] {
+        let layout = match (self.layout, self.sample_type) {
+            (ChannelLayout::Mono, _) | (ChannelLayout::Stereo, _) => None,
+            (ChannelLayout::BFormat2D(_), SampleType::MuLaw)
+            | (ChannelLayout::BFormat3D(_), SampleType::MuLaw) => Some("AL_EXT_MULAW_BFORMAT"),
+            (ChannelLayout::BFormat2D(_), _) | (ChannelLayout::BFormat3D(_), _) => {
+                Some("AL_EXT_BFORMAT")
+            }
+            (_, SampleType::MuLaw) => Some("AL_EXT_MULAW_MCFORMATS"),
+            _ => Some("AL_EXT_MCFORMATS"),
+        };
+        let unpack = match self.layout.ambisonic_order() {
+            Some(order) if order > 1 => Some("AL_SOFT_bformat_hoa"),
+            _ if self.sample_type.unpack_alignment() != 0 => Some("AL_SOFT_block_alignment"),
+            _ => None,
+        };
+        [layout, unpack, self.sample_type.extension()]
+    }
+
+    /// Returns the AL enumeration value for this format, if there is one.
+    pub(crate) fn to_al(self) -> Option<ALenum> {
+        if self.layout.ambisonic_order() == Some(0) || !self.sample_type.is_valid() {
+            return None;
+        }
+        match (self.layout, self.sample_type) {
+            (ChannelLayout::Mono, SampleType::Ima4(_)) => return Some(AL_FORMAT_MONO_IMA4),
+            (ChannelLayout::Stereo, SampleType::Ima4(_)) => return Some(AL_FORMAT_STEREO_IMA4),
+            (ChannelLayout::Mono, SampleType::MsAdpcm(_)) => {
+                return Some(AL_FORMAT_MONO_MSADPCM_SOFT)
+            }
+            (ChannelLayout::Stereo, SampleType::MsAdpcm(_)) => {
+                return Some(AL_FORMAT_STEREO_MSADPCM_SOFT)
+            }
+            (ChannelLayout::Mono, SampleType::ALaw) => return Some(AL_FORMAT_MONO_ALAW_EXT),
+            (ChannelLayout::Stereo, SampleType::ALaw) => return Some(AL_FORMAT_STEREO_ALAW_EXT),
+            (_, SampleType::ALaw) | (_, SampleType::Ima4(_)) | (_, SampleType::MsAdpcm(_)) => {
+                return None
+            }
+            _ => (),
+        }
+        let (u8_format, i16_format, f32_format, f64_format, mulaw_format) = match self.layout {
+            ChannelLayout::Mono => (
+                AL_FORMAT_MONO8,
+                AL_FORMAT_MONO16,
+                AL_FORMAT_MONO_FLOAT32,
+                Some(AL_FORMAT_MONO_DOUBLE_EXT),
+                AL_FORMAT_MONO_MULAW_EXT,
+            ),
+            ChannelLayout::Stereo => (
+                AL_FORMAT_STEREO8,
+                AL_FORMAT_STEREO16,
+                AL_FORMAT_STEREO_FLOAT32,
+                Some(AL_FORMAT_STEREO_DOUBLE_EXT),
+                AL_FORMAT_STEREO_MULAW_EXT,
+            ),
+            ChannelLayout::Rear => (
+                AL_FORMAT_REAR8,
+                AL_FORMAT_REAR16,
+                AL_FORMAT_REAR32,
+                None,
+                AL_FORMAT_REAR_MULAW,
+            ),
+            ChannelLayout::Quad => (
+                AL_FORMAT_QUAD8,
+                AL_FORMAT_QUAD16,
+                AL_FORMAT_QUAD32,
+                None,
+                AL_FORMAT_QUAD_MULAW,
+            ),
+            ChannelLayout::Surround51 => (
+                AL_FORMAT_51CHN8,
+                AL_FORMAT_51CHN16,
+                AL_FORMAT_51CHN32,
+                None,
+                AL_FORMAT_51CHN_MULAW,
+            ),
+            ChannelLayout::Surround61 => (
+                AL_FORMAT_61CHN8,
+                AL_FORMAT_61CHN16,
+                AL_FORMAT_61CHN32,
+                None,
+                AL_FORMAT_61CHN_MULAW,
+            ),
+            ChannelLayout::Surround71 => (
+                AL_FORMAT_71CHN8,
+                AL_FORMAT_71CHN16,
+                AL_FORMAT_71CHN32,
+                None,
+                AL_FORMAT_71CHN_MULAW,
+            ),
+            ChannelLayout::BFormat2D(_) => (
+                AL_FORMAT_BFORMAT2D_8,
+                AL_FORMAT_BFORMAT2D_16,
+                AL_FORMAT_BFORMAT2D_FLOAT32,
+                None,
+                AL_FORMAT_BFORMAT2D_MULAW,
+            ),
+            ChannelLayout::BFormat3D(_) => (
+                AL_FORMAT_BFORMAT3D_8,
+                AL_FORMAT_BFORMAT3D_16,
+                AL_FORMAT_BFORMAT3D_FLOAT32,
+                None,
+                AL_FORMAT_BFORMAT3D_MULAW,
+            ),
+        };
+        match self.sample_type {
+            SampleType::U8 => Some(u8_format),
+            SampleType::I16 => Some(i16_format),
+            SampleType::F32 => Some(f32_format),
+            SampleType::F64 => f64_format,
+            SampleType::MuLaw => Some(mulaw_format),
+            SampleType::ALaw | SampleType::Ima4(_) | SampleType::MsAdpcm(_) => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+/// The order in which ambisonic channels are stored.
+pub enum AmbisonicLayout {
+    /// Furse-Malham ordering (W, X, Y, Z, ...).
+    FuMa,
+    /// Ambisonic Channel Number ordering (W, Y, Z, X, ...).
+    Acn,
+}
+
+impl AmbisonicLayout {
+    pub(crate) fn to_al(self) -> ALint {
+        match self {
+            AmbisonicLayout::FuMa => AL_FUMA_SOFT,
+            AmbisonicLayout::Acn => AL_ACN_SOFT,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+/// The normalization applied to ambisonic channels.
+pub enum AmbisonicScaling {
+    /// Furse-Malham normalization.
+    FuMa,
+    /// Schmidt semi-normalization, as used by AmbiX.
+    Sn3d,
+    /// Full 3D normalization.
+    N3d,
+}
+
+impl AmbisonicScaling {
+    pub(crate) fn to_al(self) -> ALint {
+        match self {
+            AmbisonicScaling::FuMa => AL_FUMA_SOFT,
+            AmbisonicScaling::Sn3d => AL_SN3D_SOFT,
+            AmbisonicScaling::N3d => AL_N3D_SOFT,
+        }
+    }
+}
+
+/// A type that can be uploaded to a buffer as an individual PCM sample.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait Sample: Copy + private::Sealed {
+    /// The sample type corresponding to this type.
+    const TYPE: SampleType;
+}
+
+impl Sample for u8 {
+    const TYPE: SampleType = SampleType::U8;
+}
+
+impl Sample for i16 {
+    const TYPE: SampleType = SampleType::I16;
+}
+
+impl Sample for f32 {
+    const TYPE: SampleType = SampleType::F32;
+}
+
+impl Sample for f64 {
+    const TYPE: SampleType = SampleType::F64;
+}
+
+#[cfg(feature = "std")]
+/// Converts interleaved samples to dithered 16-bit PCM.
+pub(crate) fn to_i16<T: Sample>(samples: &[T]) -> Vec<i16> {
+    let mut dither = Dither::default();
+    samples
+        .iter()
+        .map(|&sample| i16::quantize(sample.to_f32(), dither.next()))
+        .collect()
+}
+
+mod private {
+    pub trait Sealed {
+        /// Returns this sample scaled to the range `[-1.0, 1.0]`.
+        fn to_f32(self) -> f32;
+    }
+
+    impl Sealed for u8 {
+        fn to_f32(self) -> f32 {
+            (f32::from(self) - 128.0) / 128.0
+        }
+    }
+
+    impl Sealed for i16 {
+        fn to_f32(self) -> f32 {
+            f32::from(self) / 32_768.0
+        }
+    }
+
+    impl Sealed for f32 {
+        fn to_f32(self) -> f32 {
+            self
+        }
+    }
+
+    impl Sealed for f64 {
+        fn to_f32(self) -> f32 {
+            self as f32
+        }
+    }
+}
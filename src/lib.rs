@@ -35,7 +35,69 @@
 )]
 
 extern crate bindgen_openal_sys;
+#[cfg(feature = "flac")]
+extern crate claxon;
+#[cfg(feature = "vorbis")]
+extern crate lewton;
+extern crate libm;
+#[cfg(feature = "std")]
+extern crate std;
+#[cfg(feature = "mp3")]
+extern crate symphonia_bundle_mp3;
+#[cfg(feature = "mp3")]
+extern crate symphonia_core;
 
+mod buffer;
+mod context;
+mod convert;
 mod device;
+mod distance;
+mod effect;
+mod error;
+mod ext;
+mod filter;
+#[cfg(feature = "flac")]
+mod flac;
+mod format;
+mod map;
+#[cfg(feature = "mp3")]
+mod mp3;
+#[cfg(any(feature = "vorbis", feature = "flac", feature = "mp3"))]
+mod pcm;
+mod resample;
+mod slot;
+mod source;
+mod stream;
+mod util;
+#[cfg(feature = "vorbis")]
+mod vorbis;
+mod wav;
 
-pub use device::Device;
+pub use buffer::{Buffer, BufferInfo};
+pub use context::Context;
+pub use convert::{
+    convert, decode_alaw, decode_mulaw, deinterleave, interleave, remix, Dither, InputSample,
+    OutputSample, I24,
+};
+pub use device::{Device, DeviceTime};
+pub use distance::{Attenuation, DistanceModel};
+pub use effect::{
+    Autowah, Chorus, Compressor, Distortion, EaxReverb, Echo, Effect, EffectType, Equalizer,
+    Flanger, FrequencyShifter, Phoneme, PitchShifter, Reverb, RingModulator, RingModulatorWaveform,
+    ShiftDirection, VocalMorpher, Waveform,
+};
+pub use error::Error;
+pub use filter::{BandPass, Filter, FilterType, HighPass, LowPass};
+#[cfg(feature = "flac")]
+pub use flac::FlacDecoder;
+pub use format::{AmbisonicLayout, AmbisonicScaling, ChannelLayout, Format, Sample, SampleType};
+pub use map::{BufferMap, MapFlags};
+#[cfg(feature = "mp3")]
+pub use mp3::Mp3Decoder;
+pub use resample::Resampler;
+pub use slot::AuxiliaryEffectSlot;
+pub use source::{Source, SourceFrameOffset, SourceOffset, SourceState};
+pub use stream::{Decoder, Stream};
+#[cfg(feature = "vorbis")]
+pub use vorbis::VorbisDecoder;
+pub use wav::{Wav, WavDecoder, WavError};
@@ -0,0 +1,127 @@
+// Copyright (c) 2018 FaultyRAM
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the
+// MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at
+// your option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use bindgen_openal_sys::{ALsizei, ALuint};
+use context::Context;
+use core::cell::Cell;
+use core::convert::TryFrom;
+use core::mem::size_of;
+use core::ops::{Deref, DerefMut, Range};
+use core::slice;
+use error::Error;
+use ext::{
+    ALbitfieldSOFT, FlushMappedBufferFn, UnmapBufferFn, AL_MAP_PERSISTENT_BIT_SOFT,
+    AL_MAP_READ_BIT_SOFT, AL_MAP_WRITE_BIT_SOFT,
+};
+use format::Sample;
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+/// The ways in which a buffer's storage may be mapped into memory.
+pub struct MapFlags {
+    /// Allows mapped data to be read.
+    pub read: bool,
+    /// Allows mapped data to be written.
+    pub write: bool,
+    /// Allows the buffer to stay mapped while it is being played.
+    ///
+    /// Writes to a persistent mapping only become visible to the mixer once they are flushed.
+    pub persistent: bool,
+}
+
+impl MapFlags {
+    pub(crate) fn to_al(self) -> ALbitfieldSOFT {
+        let mut bits = 0;
+        if self.read {
+            bits |= AL_MAP_READ_BIT_SOFT;
+        }
+        if self.write {
+            bits |= AL_MAP_WRITE_BIT_SOFT;
+        }
+        if self.persistent {
+            bits |= AL_MAP_PERSISTENT_BIT_SOFT;
+        }
+        bits
+    }
+}
+
+#[derive(Debug)]
+/// The storage of a buffer, mapped into memory as a slice of samples that can be read and
+/// written.
+///
+/// Only one mapping of a buffer can exist at a time, and its contents can't be replaced while it
+/// is mapped. The buffer is unmapped when this is dropped.
+pub struct BufferMap<'b, T> {
+    context: &'b Context<'b>,
+    name: ALuint,
+    mapped: &'b Cell<bool>,
+    data: *mut T,
+    len: usize,
+    unmap: UnmapBufferFn,
+    flush: Option<FlushMappedBufferFn>,
+}
+
+impl<'b, T: Sample> BufferMap<'b, T> {
+    pub(crate) fn new(
+        context: &'b Context<'b>,
+        name: ALuint,
+        mapped: &'b Cell<bool>,
+        data: *mut T,
+        len: usize,
+        unmap: UnmapBufferFn,
+        flush: Option<FlushMappedBufferFn>,
+    ) -> Self {
+        Self {
+            context,
+            name,
+            mapped,
+            data,
+            len,
+            unmap,
+            flush,
+        }
+    }
+
+    /// Makes writes to a range of samples visible to the mixer.
+    ///
+    /// This is only needed for persistent mappings, and returns `Error::InvalidOperation` for any
+    /// other kind.
+    pub fn flush(&self, range: Range<usize>) -> Result<(), Error> {
+        let flush = self.flush.ok_or(Error::InvalidOperation)?;
+        if range.start > range.end || range.end > self.len {
+            return Err(Error::InvalidValue);
+        }
+        let offset =
+            ALsizei::try_from(range.start * size_of::<T>()).map_err(|_| Error::InvalidValue)?;
+        let length = ALsizei::try_from((range.end - range.start) * size_of::<T>())
+            .map_err(|_| Error::InvalidValue)?;
+        let name = self.name;
+        self.context.call(|| unsafe { flush(name, offset, length) })
+    }
+}
+
+impl<'b, T> Deref for BufferMap<'b, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.data, self.len) }
+    }
+}
+
+impl<'b, T> DerefMut for BufferMap<'b, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.data, self.len) }
+    }
+}
+
+impl<'b, T> Drop for BufferMap<'b, T> {
+    fn drop(&mut self) {
+        let (name, unmap) = (self.name, self.unmap);
+        let _ = self.context.call(|| unsafe { unmap(name) });
+        self.mapped.set(false);
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright (c) 2018 FaultyRAM
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the
+// MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at
+// your option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::fmt::{self, Debug, Formatter};
+use error::Error;
+use format::{ChannelLayout, Format, SampleType};
+use pcm::PcmQueue;
+use std::boxed::Box;
+use std::io::{self, Read};
+use stream::Decoder;
+use symphonia_bundle_mp3::{MpaDecoder, MpaReader};
+use symphonia_core::audio::SampleBuffer;
+use symphonia_core::codecs::{Decoder as CodecDecoder, DecoderOptions};
+use symphonia_core::errors::Error as SymphoniaError;
+use symphonia_core::formats::{FormatOptions, FormatReader};
+use symphonia_core::io::{MediaSourceStream, MediaSourceStreamOptions, ReadOnlySource};
+
+/// A decoder for MP3 audio data.
+///
+/// Mono and stereo streams are supported, and are decoded to 16-bit samples.
+pub struct Mp3Decoder {
+    reader: MpaReader,
+    decoder: MpaDecoder,
+    format: Format,
+    frequency: u32,
+    queue: PcmQueue,
+}
+
+impl Mp3Decoder {
+    /// Reads the first MP3 frame header from `reader` and creates a decoder for the stream.
+    pub fn new<R: Read + Send + Sync + 'static>(reader: R) -> Result<Self, Error> {
+        let source = MediaSourceStream::new(
+            Box::new(ReadOnlySource::new(reader)),
+            MediaSourceStreamOptions::default(),
+        );
+        let reader = MpaReader::try_new(source, &FormatOptions::default()).map_err(Error::Mp3)?;
+        let (decoder, frequency, channels) = {
+            let params = &reader
+                .default_track()
+                .ok_or(Error::Mp3(SymphoniaError::Unsupported("no MP3 track")))?
+                .codec_params;
+            let decoder =
+                MpaDecoder::try_new(params, &DecoderOptions::default()).map_err(Error::Mp3)?;
+            let frequency = params
+                .sample_rate
+                .ok_or(Error::Mp3(SymphoniaError::Unsupported(
+                    "unknown sample rate",
+                )))?;
+            (decoder, frequency, params.channels.map_or(0, |c| c.count()))
+        };
+        let layout = match channels {
+            1 | 2 => ChannelLayout::from_channels(channels),
+            _ => None,
+        }
+        .ok_or(Error::UnsupportedChannels(channels))?;
+        Ok(Self {
+            reader,
+            decoder,
+            format: Format::new(layout, SampleType::I16),
+            frequency,
+            queue: PcmQueue::default(),
+        })
+    }
+}
+
+impl Debug for Mp3Decoder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mp3Decoder")
+            .field("format", &self.format)
+            .field("frequency", &self.frequency)
+            .finish()
+    }
+}
+
+impl Decoder for Mp3Decoder {
+    fn format(&self) -> Format {
+        self.format
+    }
+
+    fn frequency(&self) -> u32 {
+        self.frequency
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        let reader = &mut self.reader;
+        let decoder = &mut self.decoder;
+        let channels = self.format.layout.channels();
+        self.queue.read(buf, channels, |samples| loop {
+            let packet = match reader.next_packet() {
+                Ok(packet) => packet,
+                Err(SymphoniaError::IoError(ref e)) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                    return Ok(false)
+                }
+                Err(e) => return Err(Error::Mp3(e)),
+            };
+            match decoder.decode(&packet) {
+                Ok(decoded) => {
+                    let spec = *decoded.spec();
+                    if spec.channels.count() != channels {
+                        return Err(Error::UnsupportedChannels(spec.channels.count()));
+                    }
+                    let mut decoded_samples = SampleBuffer::new(decoded.capacity() as u64, spec);
+                    decoded_samples.copy_interleaved_ref(decoded);
+                    samples.extend_from_slice(decoded_samples.samples());
+                    return Ok(true);
+                }
+                // Corrupt frames are skipped rather than ending the stream.
+                Err(SymphoniaError::DecodeError(_)) => continue,
+                Err(e) => return Err(Error::Mp3(e)),
+            }
+        })
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright (c) 2018 FaultyRAM
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the
+// MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at
+// your option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use error::Error;
+use std::vec::Vec;
+use util;
+
+#[derive(Debug, Default)]
+/// Interleaved 16-bit samples that have been decoded but not yet read.
+pub(crate) struct PcmQueue {
+    samples: Vec<i16>,
+    offset: usize,
+}
+
+impl PcmQueue {
+    /// Copies whole frames into `buf`, calling `decode` to refill the queue whenever it runs dry.
+    ///
+    /// `decode` is passed an empty vector to fill with the next block of samples, and returns
+    /// `false` once there is nothing left to decode. Returns the number of bytes written, or
+    /// `Error::InvalidValue` if `buf` can't hold a whole frame.
+    pub(crate) fn read<F>(
+        &mut self,
+        buf: &mut [u8],
+        channels: usize,
+        mut decode: F,
+    ) -> Result<usize, Error>
+    where
+        F: FnMut(&mut Vec<i16>) -> Result<bool, Error>,
+    {
+        let frame_size = channels * 2;
+        if buf.len() < frame_size {
+            return Err(Error::InvalidValue);
+        }
+        let mut written = 0;
+        while buf.len() - written >= frame_size {
+            if self.offset == self.samples.len() {
+                self.samples.clear();
+                self.offset = 0;
+                if !decode(&mut self.samples)? {
+                    break;
+                }
+                continue;
+            }
+            let frames = (buf.len() - written) / frame_size;
+            let available = &self.samples[self.offset..];
+            let count = (frames * channels).min(available.len());
+            let bytes = util::as_bytes(&available[..count]);
+            buf[written..written + bytes.len()].copy_from_slice(bytes);
+            written += bytes.len();
+            self.offset += count;
+        }
+        Ok(written)
+    }
+}
@@ -0,0 +1,278 @@
+// Copyright (c) 2018 FaultyRAM
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the
+// MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at
+// your option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::f64::consts::PI;
+use core::fmt::{self, Debug, Formatter};
+use error::Error;
+use libm;
+
+/// The number of input samples on either side of an output sample that contribute to it.
+const HALF: usize = 16;
+/// The number of input samples that contribute to each output sample.
+const TAPS: usize = HALF * 2;
+/// The number of kernel values computed between adjacent input samples.
+const PHASES: usize = 64;
+/// The number of entries in the kernel table.
+const KERNEL_LEN: usize = HALF * PHASES + 1;
+/// The maximum number of channels a `Resampler` can process.
+const MAX_CHANNELS: usize = 8;
+
+/// Converts interleaved `f32` audio data from one sample rate to another.
+///
+/// This uses a Blackman-windowed sinc filter, which also removes frequencies above the output's
+/// Nyquist limit when downsampling. Because the filter looks ahead of the current position, output
+/// lags behind input; call `flush` once all input has been processed to retrieve the remainder.
+pub struct Resampler {
+    from: u32,
+    to: u32,
+    channels: usize,
+    step: f64,
+    position: f64,
+    tail: Option<usize>,
+    history: [[f32; TAPS]; MAX_CHANNELS],
+    kernel: [f32; KERNEL_LEN],
+}
+
+impl Resampler {
+    /// Creates a new resampler that converts from one sample rate to another.
+    ///
+    /// Returns `Error::InvalidValue` if either rate is zero, or `Error::UnsupportedChannels` if
+    /// `channels` is zero or more than eight.
+    pub fn new(from: u32, to: u32, channels: usize) -> Result<Self, Error> {
+        if from == 0 || to == 0 {
+            return Err(Error::InvalidValue);
+        }
+        if channels == 0 || channels > MAX_CHANNELS {
+            return Err(Error::UnsupportedChannels(channels));
+        }
+        let cutoff = if to < from {
+            f64::from(to) / f64::from(from)
+        } else {
+            1.0
+        };
+        let mut kernel = [0.0; KERNEL_LEN];
+        for (i, value) in kernel.iter_mut().enumerate() {
+            let x = i as f64 / PHASES as f64;
+            let window = 0.42
+                + 0.5 * libm::cos(PI * x / HALF as f64)
+                + 0.08 * libm::cos(2.0 * PI * x / HALF as f64);
+            let sinc = if i == 0 {
+                1.0
+            } else {
+                libm::sin(PI * cutoff * x) / (PI * cutoff * x)
+            };
+            *value = (cutoff * sinc * window) as f32;
+        }
+        kernel[KERNEL_LEN - 1] = 0.0;
+        Ok(Self {
+            from,
+            to,
+            channels,
+            step: f64::from(from) / f64::from(to),
+            position: TAPS as f64,
+            tail: None,
+            history: [[0.0; TAPS]; MAX_CHANNELS],
+            kernel,
+        })
+    }
+
+    /// Returns the input sample rate.
+    pub fn from(&self) -> u32 {
+        self.from
+    }
+
+    /// Returns the output sample rate.
+    pub fn to(&self) -> u32 {
+        self.to
+    }
+
+    /// Returns the number of channels in each frame.
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Discards any buffered input, so that the next call to `process` starts a new stream.
+    pub fn reset(&mut self) {
+        self.position = TAPS as f64;
+        self.tail = None;
+        self.history = [[0.0; TAPS]; MAX_CHANNELS];
+    }
+
+    /// Resamples as much of `src` into `dst` as possible.
+    ///
+    /// Returns the number of samples (not frames) read from `src` and written to `dst`. Processing
+    /// stops once either all of `src` has been read or `dst` is full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `src` or `dst` is not a multiple of the channel count.
+    pub fn process(&mut self, src: &[f32], dst: &mut [f32]) -> (usize, usize) {
+        assert_eq!(
+            src.len() % self.channels,
+            0,
+            "source does not contain whole frames"
+        );
+        // New input extends the stream, so a later `flush` has a full filter tail to write.
+        self.tail = None;
+        let mut frames = src.chunks(self.channels);
+        self.run(|| frames.next(), dst)
+    }
+
+    /// Writes any output still buffered by the filter into `dst`, once all input has been passed to
+    /// `process`.
+    ///
+    /// Returns the number of samples written. If this is equal to the length of `dst`, there may
+    /// be more output remaining and `flush` should be called again. Calling `process` afterwards
+    /// continues the stream after the flushed silence; call `reset` first to start a new one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `dst` is not a multiple of the channel count.
+    pub fn flush(&mut self, dst: &mut [f32]) -> usize {
+        let silence = [0.0; MAX_CHANNELS];
+        let silence = &silence[..self.channels];
+        let mut remaining = self.tail.unwrap_or(HALF);
+        let (_, produced) = self.run(
+            || {
+                if remaining == 0 {
+                    None
+                } else {
+                    remaining -= 1;
+                    Some(silence)
+                }
+            },
+            dst,
+        );
+        self.tail = Some(remaining);
+        produced
+    }
+
+    /// Pushes frames from `next` through the filter until it runs dry or `dst` is full.
+    fn run<'a, F: FnMut() -> Option<&'a [f32]>>(
+        &mut self,
+        mut next: F,
+        dst: &mut [f32],
+    ) -> (usize, usize) {
+        assert_eq!(
+            dst.len() % self.channels,
+            0,
+            "destination does not contain whole frames"
+        );
+        let mut consumed = 0;
+        let mut produced = 0;
+        while produced < dst.len() {
+            while self.position >= HALF as f64 {
+                match next() {
+                    Some(frame) => {
+                        for (history, &sample) in self.history.iter_mut().zip(frame) {
+                            history.copy_within(1.., 0);
+                            history[TAPS - 1] = sample;
+                        }
+                        self.position -= 1.0;
+                        consumed += self.channels;
+                    }
+                    None => return (consumed, produced),
+                }
+            }
+            for channel in 0..self.channels {
+                dst[produced + channel] = self.filter(channel);
+            }
+            produced += self.channels;
+            self.position += self.step;
+        }
+        (consumed, produced)
+    }
+
+    /// Computes the output sample for a channel at the current position.
+    fn filter(&self, channel: usize) -> f32 {
+        let mut sum = 0.0;
+        for (i, &sample) in self.history[channel].iter().enumerate() {
+            let distance = libm::fabs(self.position - i as f64) * PHASES as f64;
+            let index = distance as usize;
+            if index < KERNEL_LEN - 1 {
+                let fraction = (distance - index as f64) as f32;
+                let (a, b) = (self.kernel[index], self.kernel[index + 1]);
+                sum += sample * (a + (b - a) * fraction);
+            }
+        }
+        sum
+    }
+}
+
+impl Debug for Resampler {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Resampler")
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .field("channels", &self.channels)
+            .field("position", &self.position)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAMES: usize = 4096;
+
+    #[test]
+    fn new_rejects_invalid_parameters() {
+        match Resampler::new(0, 48_000, 2) {
+            Err(Error::InvalidValue) => {}
+            result => panic!("{:?}", result),
+        }
+        match Resampler::new(44_100, 0, 2) {
+            Err(Error::InvalidValue) => {}
+            result => panic!("{:?}", result),
+        }
+        match Resampler::new(44_100, 48_000, 0) {
+            Err(Error::UnsupportedChannels(0)) => {}
+            result => panic!("{:?}", result),
+        }
+        match Resampler::new(44_100, 48_000, 9) {
+            Err(Error::UnsupportedChannels(9)) => {}
+            result => panic!("{:?}", result),
+        }
+    }
+
+    #[test]
+    fn flush_after_further_input() {
+        let mut resampler = Resampler::new(44_100, 48_000, 1).unwrap();
+        let mut dst = [0.0; 256];
+        let _ = resampler.process(&[0.5; 64], &mut dst);
+        assert!(resampler.flush(&mut dst) > 0);
+        assert_eq!(resampler.flush(&mut dst), 0);
+        let _ = resampler.process(&[0.5; 64], &mut dst);
+        assert!(resampler.flush(&mut dst) > 0);
+    }
+
+    #[test]
+    fn preserves_length_and_amplitude() {
+        for &(from, to) in &[(44_100, 48_000), (48_000, 22_050), (48_000, 48_000)] {
+            let mut src = [0.0; FRAMES * 2];
+            for (i, frame) in src.chunks_mut(2).enumerate() {
+                let phase = i as f32 * 2.0 * core::f32::consts::PI * 440.0 / from as f32;
+                frame[0] = libm::sinf(phase);
+                frame[1] = -frame[0];
+            }
+            let mut resampler = Resampler::new(from, to, 2).unwrap();
+            let mut dst = [0.0; FRAMES * 4];
+            let (consumed, produced) = resampler.process(&src, &mut dst);
+            let flushed = resampler.flush(&mut dst[produced..]);
+            assert_eq!(consumed, src.len());
+
+            let expected = FRAMES as i64 * i64::from(to) / i64::from(from);
+            let frames = ((produced + flushed) / 2) as i64;
+            assert!((frames - expected).abs() <= 1, "{} != {}", frames, expected);
+
+            let peak = dst[..produced].iter().fold(0.0f32, |peak, &v| peak.max(v));
+            assert!((peak - 1.0).abs() < 0.01, "peak {}", peak);
+        }
+    }
+}
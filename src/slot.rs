@@ -0,0 +1,97 @@
+// Copyright (c) 2018 FaultyRAM
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the
+// MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at
+// your option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use bindgen_openal_sys::{ALenum, ALint, ALuint};
+use context::Context;
+use effect::Effect;
+use error::Error;
+use ext::{
+    AuxiliaryEffectSlotfFn, AuxiliaryEffectSlotiFn, DeleteAuxiliaryEffectSlotsFn,
+    GenAuxiliaryEffectSlotsFn, AL_EFFECTSLOT_AUXILIARY_SEND_AUTO, AL_EFFECTSLOT_EFFECT,
+    AL_EFFECTSLOT_GAIN,
+};
+
+#[derive(Debug)]
+/// A processor that applies an effect to the sources sent to it.
+///
+/// Sources are routed to a slot with `Source::set_send`. Requires `ALC_EXT_EFX`.
+pub struct AuxiliaryEffectSlot<'a> {
+    context: &'a Context<'a>,
+    name: ALuint,
+    delete: DeleteAuxiliaryEffectSlotsFn,
+    sloti: AuxiliaryEffectSlotiFn,
+    slotf: AuxiliaryEffectSlotfFn,
+}
+
+impl<'a> AuxiliaryEffectSlot<'a> {
+    /// Creates a new effect slot with no effect loaded.
+    pub fn new(context: &'a Context<'a>) -> Result<Self, Error> {
+        let (gen, delete, sloti, slotf) = unsafe {
+            (
+                context.extension_function::<GenAuxiliaryEffectSlotsFn>(
+                    "ALC_EXT_EFX",
+                    "alGenAuxiliaryEffectSlots",
+                )?,
+                context.extension_function("ALC_EXT_EFX", "alDeleteAuxiliaryEffectSlots")?,
+                context.extension_function("ALC_EXT_EFX", "alAuxiliaryEffectSloti")?,
+                context.extension_function("ALC_EXT_EFX", "alAuxiliaryEffectSlotf")?,
+            )
+        };
+        let mut name = 0;
+        context
+            .call(|| unsafe { gen(1, &mut name) })
+            .map(|()| Self {
+                context,
+                name,
+                delete,
+                sloti,
+                slotf,
+            })
+    }
+
+    /// Loads an effect into this slot, or unloads the current effect if `effect` is `None`.
+    ///
+    /// The effect's parameters are copied when it is loaded, so later changes to it require
+    /// loading it again.
+    pub fn set_effect(&self, effect: Option<&Effect<'_>>) -> Result<(), Error> {
+        let effect = effect.map_or(0, Effect::name);
+        self.seti(AL_EFFECTSLOT_EFFECT, effect as ALint)
+    }
+
+    /// Sets the output level of this slot, from 0 to 1.
+    pub fn set_gain(&self, gain: f32) -> Result<(), Error> {
+        if !(0.0..=1.0).contains(&gain) {
+            return Err(Error::InvalidValue);
+        }
+        let (name, slotf) = (self.name, self.slotf);
+        self.context
+            .call(|| unsafe { slotf(name, AL_EFFECTSLOT_GAIN, gain) })
+    }
+
+    /// Sets whether the send level of sources to this slot is adjusted automatically for their
+    /// distance from the listener. This is enabled by default.
+    pub fn set_send_auto(&self, enabled: bool) -> Result<(), Error> {
+        self.seti(AL_EFFECTSLOT_AUXILIARY_SEND_AUTO, ALint::from(enabled))
+    }
+
+    pub(crate) fn name(&self) -> ALuint {
+        self.name
+    }
+
+    fn seti(&self, param: ALenum, value: ALint) -> Result<(), Error> {
+        let (name, sloti) = (self.name, self.sloti);
+        self.context.call(|| unsafe { sloti(name, param, value) })
+    }
+}
+
+impl<'a> Drop for AuxiliaryEffectSlot<'a> {
+    fn drop(&mut self) {
+        let (name, delete) = (self.name, self.delete);
+        let _ = self.context.call(|| unsafe { delete(1, &name) });
+    }
+}
@@ -0,0 +1,470 @@
+// Copyright (c) 2018 FaultyRAM
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the
+// MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at
+// your option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use bindgen_openal_sys::{
+    alDeleteSources, alGenSources, alGetBufferi, alGetSourcef, alGetSourcefv, alGetSourcei,
+    alSource3i, alSourcePause, alSourcePausev, alSourcePlay, alSourcePlayv, alSourceQueueBuffers,
+    alSourceRewind, alSourceRewindv, alSourceStop, alSourceStopv, alSourceUnqueueBuffers,
+    alSourcefv, alSourcei, ALenum, ALfloat, ALint, ALsizei, ALuint, AL_BUFFER,
+    AL_BUFFERS_PROCESSED, AL_BUFFERS_QUEUED, AL_FREQUENCY, AL_INITIAL, AL_ORIENTATION, AL_PAUSED,
+    AL_PLAYING, AL_SAMPLE_OFFSET, AL_SEC_OFFSET, AL_SOURCE_STATE, AL_STOPPED,
+};
+use buffer::Buffer;
+use context::Context;
+use core::convert::TryFrom;
+use core::ptr;
+use core::time::Duration;
+use distance::DistanceModel;
+use error::Error;
+use ext::{
+    ALint64SOFT, GetSourcedvFn, GetSourcei64vFn, SourcePlayAtTimeFn, SourcePlayAtTimevFn,
+    AL_AUXILIARY_SEND_FILTER, AL_DIRECT_FILTER, AL_SAMPLE_OFFSET_LATENCY_SOFT,
+    AL_SEC_OFFSET_LATENCY_SOFT, AL_SOURCE_DISTANCE_MODEL,
+};
+use filter::Filter;
+use slot::AuxiliaryEffectSlot;
+
+/// The maximum number of sources that can be controlled by a single batched call.
+const MAX_BATCH_SOURCES: usize = 256;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+/// The playback state of a source.
+pub enum SourceState {
+    /// The source has not been played since it was created or rewound.
+    Initial,
+    /// The source is playing.
+    Playing,
+    /// The source is paused.
+    Paused,
+    /// The source has stopped, either explicitly or by reaching the end of its data.
+    Stopped,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+/// A source's playback position, paired with the time until audio at that position is heard.
+pub struct SourceOffset {
+    /// The playback position, as the time from the start of the attached buffer or queue.
+    pub offset: Duration,
+    /// The time between the mixer reaching `offset` and the audio reaching the output.
+    pub latency: Duration,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+/// A source's playback position in frames, paired with the time until audio at that position is
+/// heard.
+pub struct SourceFrameOffset {
+    /// The playback position, as the number of frames from the start of the attached buffer or
+    /// queue.
+    pub frame: u64,
+    /// The time between the mixer reaching `frame` and the audio reaching the output.
+    pub latency: Duration,
+}
+
+#[derive(Debug)]
+/// An emitter of sound within a context.
+pub struct Source<'a> {
+    context: &'a Context<'a>,
+    name: ALuint,
+}
+
+impl<'a> Source<'a> {
+    /// Creates a new source.
+    pub fn new(context: &'a Context<'a>) -> Result<Self, Error> {
+        let mut name = 0;
+        context
+            .call(|| unsafe { alGenSources(1, &mut name) })
+            .map(|()| Self { context, name })
+    }
+
+    /// Attaches a buffer to this source, or detaches the current buffer if `buffer` is `None`.
+    ///
+    /// Any buffers queued on this source are released.
+    pub fn set_buffer(&mut self, buffer: Option<&'a Buffer<'a>>) -> Result<(), Error> {
+        let buffer = buffer.map_or(0, Buffer::name);
+        self.seti(AL_BUFFER, buffer as ALint)
+    }
+
+    /// Applies a filter to the direct path of this source, or removes it if `filter` is `None`.
+    ///
+    /// The filter stays borrowed for as long as this source exists, so it can't be deleted while
+    /// attached. Its parameters are copied when it is attached, so later changes made with
+    /// `Filter::set` require attaching it again. Requires `ALC_EXT_EFX`.
+    pub fn set_direct_filter(&mut self, filter: Option<&'a Filter<'a>>) -> Result<(), Error> {
+        self.context.require_extension("ALC_EXT_EFX")?;
+        let filter = filter.map_or(0, Filter::name);
+        self.seti(AL_DIRECT_FILTER, filter as ALint)
+    }
+
+    /// Routes this source to an effect slot through one of its auxiliary sends, optionally
+    /// filtered, or disconnects the send if `slot` is `None`.
+    ///
+    /// `index` must be less than `Context::auxiliary_sends`, and a filter can only be given along
+    /// with a slot; otherwise `Error::InvalidValue` is returned. The slot and filter stay borrowed
+    /// for as long as this source exists, and the filter's parameters are copied when it is
+    /// attached, as with `set_direct_filter`. Requires `ALC_EXT_EFX`.
+    pub fn set_send(
+        &mut self,
+        index: usize,
+        slot: Option<&'a AuxiliaryEffectSlot<'a>>,
+        filter: Option<&'a Filter<'a>>,
+    ) -> Result<(), Error> {
+        self.context.require_extension("ALC_EXT_EFX")?;
+        if index >= self.context.auxiliary_sends() || (slot.is_none() && filter.is_some()) {
+            return Err(Error::InvalidValue);
+        }
+        let slot = slot.map_or(0, AuxiliaryEffectSlot::name) as ALint;
+        let filter = filter.map_or(0, Filter::name) as ALint;
+        let (name, index) = (self.name, index as ALint);
+        self.context
+            .call(|| unsafe { alSource3i(name, AL_AUXILIARY_SEND_FILTER, slot, index, filter) })
+    }
+
+    /// Starts or resumes playback.
+    pub fn play(&self) -> Result<(), Error> {
+        let name = self.name;
+        self.context.call(|| unsafe { alSourcePlay(name) })
+    }
+
+    /// Starts playback once the device clock reaches `start`, as returned by `Device::clock`.
+    ///
+    /// If `start` has already passed, playback begins immediately. Requires
+    /// `AL_SOFT_source_start_delay`.
+    pub fn play_at(&self, start: Duration) -> Result<(), Error> {
+        let play: SourcePlayAtTimeFn = unsafe {
+            self.context
+                .extension_function("AL_SOFT_source_start_delay", "alSourcePlayAtTimeSOFT")?
+        };
+        let start = device_nanos(start)?;
+        let name = self.name;
+        self.context.call(|| unsafe { play(name, start) })
+    }
+
+    /// Starts playback of several sources together once the device clock reaches `start`.
+    ///
+    /// All of the sources must belong to `context`, and at most 256 may be started at once.
+    /// Requires `AL_SOFT_source_start_delay`, which is checked even if `sources` is empty.
+    pub fn play_all_at(
+        context: &'a Context<'a>,
+        sources: &[&Self],
+        start: Duration,
+    ) -> Result<(), Error> {
+        let play: SourcePlayAtTimevFn = unsafe {
+            context.extension_function("AL_SOFT_source_start_delay", "alSourcePlayAtTimevSOFT")?
+        };
+        let start = device_nanos(start)?;
+        Self::batch(sources, |sources_context, n, names| {
+            if !ptr::eq(sources_context, context) {
+                return Err(Error::InvalidOperation);
+            }
+            context.call(|| unsafe { play(n, names, start) })
+        })
+    }
+
+    /// Pauses playback.
+    pub fn pause(&self) -> Result<(), Error> {
+        let name = self.name;
+        self.context.call(|| unsafe { alSourcePause(name) })
+    }
+
+    /// Stops playback.
+    pub fn stop(&self) -> Result<(), Error> {
+        let name = self.name;
+        self.context.call(|| unsafe { alSourceStop(name) })
+    }
+
+    /// Stops playback and returns this source to its initial state.
+    pub fn rewind(&self) -> Result<(), Error> {
+        let name = self.name;
+        self.context.call(|| unsafe { alSourceRewind(name) })
+    }
+
+    /// Starts or resumes playback of several sources in the same mixing update.
+    ///
+    /// All of the sources must belong to the same context, and at most 256 may be controlled at
+    /// once.
+    pub fn play_all(sources: &[&Self]) -> Result<(), Error> {
+        Self::batch(sources, |context, n, names| {
+            context.call(|| unsafe { alSourcePlayv(n, names) })
+        })
+    }
+
+    /// Pauses playback of several sources in the same mixing update.
+    ///
+    /// The same restrictions as `play_all` apply.
+    pub fn pause_all(sources: &[&Self]) -> Result<(), Error> {
+        Self::batch(sources, |context, n, names| {
+            context.call(|| unsafe { alSourcePausev(n, names) })
+        })
+    }
+
+    /// Stops playback of several sources in the same mixing update.
+    ///
+    /// The same restrictions as `play_all` apply.
+    pub fn stop_all(sources: &[&Self]) -> Result<(), Error> {
+        Self::batch(sources, |context, n, names| {
+            context.call(|| unsafe { alSourceStopv(n, names) })
+        })
+    }
+
+    /// Stops playback of several sources and returns them to their initial state.
+    ///
+    /// The same restrictions as `play_all` apply.
+    pub fn rewind_all(sources: &[&Self]) -> Result<(), Error> {
+        Self::batch(sources, |context, n, names| {
+            context.call(|| unsafe { alSourceRewindv(n, names) })
+        })
+    }
+
+    /// Moves the playback position to the given time from the start of the attached buffer or
+    /// queue.
+    ///
+    /// The time is converted to a frame offset using the sample rate of the current buffer, so
+    /// this fails with `Error::InvalidName` if no buffer is attached or queued.
+    pub fn seek(&self, position: Duration) -> Result<(), Error> {
+        let buffer = self.geti(AL_BUFFER)? as ALuint;
+        let mut frequency = 0;
+        self.context
+            .call(|| unsafe { alGetBufferi(buffer, AL_FREQUENCY, &mut frequency) })?;
+        self.seek_frames(frames_from_duration(position, frequency.max(0) as u32))
+    }
+
+    /// Moves the playback position to the given frame from the start of the attached buffer or
+    /// queue.
+    pub fn seek_frames(&self, frame: u64) -> Result<(), Error> {
+        let frame = ALint::try_from(frame).map_err(|_| Error::InvalidValue)?;
+        self.seti(AL_SAMPLE_OFFSET, frame)
+    }
+
+    /// Returns the playback position, as the time from the start of the attached buffer or
+    /// queue.
+    ///
+    /// Buffers removed from the queue no longer count towards the position; `Stream::position`
+    /// accounts for them.
+    pub fn position(&self) -> Result<Duration, Error> {
+        self.getf(AL_SEC_OFFSET)
+            .map(|secs| duration_from_secs(f64::from(secs)))
+    }
+
+    /// Returns the playback position, as the number of frames from the start of the attached
+    /// buffer or queue.
+    pub fn frame_position(&self) -> Result<u64, Error> {
+        self.geti(AL_SAMPLE_OFFSET).map(|frame| frame.max(0) as u64)
+    }
+
+    /// Returns the playback position together with the output latency, read atomically.
+    ///
+    /// Without `AL_SOFT_source_latency`, this falls back to `position` with a latency of zero,
+    /// which is only as precise as the implementation's mixing updates.
+    pub fn offset_with_latency(&self) -> Result<SourceOffset, Error> {
+        let get: GetSourcedvFn = match unsafe {
+            self.context
+                .extension_function("AL_SOFT_source_latency", "alGetSourcedvSOFT")
+        } {
+            Ok(get) => get,
+            Err(Error::ExtensionNotPresent(_)) => {
+                return self.position().map(|offset| SourceOffset {
+                    offset,
+                    latency: Duration::from_secs(0),
+                })
+            }
+            Err(e) => return Err(e),
+        };
+        let name = self.name;
+        let mut values = [0.0; 2];
+        self.context
+            .call(|| unsafe { get(name, AL_SEC_OFFSET_LATENCY_SOFT, values.as_mut_ptr()) })
+            .map(|()| SourceOffset {
+                offset: duration_from_secs(values[0]),
+                latency: duration_from_secs(values[1]),
+            })
+    }
+
+    /// Returns the playback position in frames together with the output latency, read
+    /// atomically.
+    ///
+    /// Without `AL_SOFT_source_latency`, this falls back to `frame_position` with a latency of
+    /// zero.
+    pub fn frame_offset_with_latency(&self) -> Result<SourceFrameOffset, Error> {
+        let get: GetSourcei64vFn = match unsafe {
+            self.context
+                .extension_function("AL_SOFT_source_latency", "alGetSourcei64vSOFT")
+        } {
+            Ok(get) => get,
+            Err(Error::ExtensionNotPresent(_)) => {
+                return self.frame_position().map(|frame| SourceFrameOffset {
+                    frame,
+                    latency: Duration::from_secs(0),
+                })
+            }
+            Err(e) => return Err(e),
+        };
+        let name = self.name;
+        let mut values = [0; 2];
+        self.context
+            .call(|| unsafe { get(name, AL_SAMPLE_OFFSET_LATENCY_SOFT, values.as_mut_ptr()) })
+            .map(|()| SourceFrameOffset {
+                // The offset is 32.32 fixed-point; the latency is in nanoseconds.
+                frame: (values[0].max(0) >> 32) as u64,
+                latency: Duration::from_nanos(values[1].max(0) as u64),
+            })
+    }
+
+    /// Sets the orientation of this source, as "at" and "up" vectors.
+    ///
+    /// This rotates the sound field of B-Format buffers played by this source. The default
+    /// orientation faces down the negative Z axis, with positive Y up.
+    pub fn set_orientation(&mut self, at: [f32; 3], up: [f32; 3]) -> Result<(), Error> {
+        let values = [at[0], at[1], at[2], up[0], up[1], up[2]];
+        let name = self.name;
+        self.context
+            .call(|| unsafe { alSourcefv(name, AL_ORIENTATION, values.as_ptr()) })
+    }
+
+    /// Returns the orientation of this source, as "at" and "up" vectors.
+    pub fn orientation(&self) -> Result<([f32; 3], [f32; 3]), Error> {
+        let name = self.name;
+        let mut values: [ALfloat; 6] = [0.0; 6];
+        self.context
+            .call(|| unsafe { alGetSourcefv(name, AL_ORIENTATION, values.as_mut_ptr()) })
+            .map(|()| {
+                (
+                    [values[0], values[1], values[2]],
+                    [values[3], values[4], values[5]],
+                )
+            })
+    }
+
+    /// Overrides the context's distance model for this source.
+    ///
+    /// The model only takes effect while per-source distance models are enabled on the context
+    /// with `Context::set_source_distance_models`. Requires `AL_EXT_source_distance_model`.
+    pub fn set_distance_model(&mut self, model: DistanceModel) -> Result<(), Error> {
+        self.context
+            .require_extension("AL_EXT_source_distance_model")?;
+        self.seti(AL_SOURCE_DISTANCE_MODEL, model.to_al())
+    }
+
+    /// Returns the distance model set for this source.
+    ///
+    /// Requires `AL_EXT_source_distance_model`.
+    pub fn distance_model(&self) -> Result<DistanceModel, Error> {
+        self.context
+            .require_extension("AL_EXT_source_distance_model")?;
+        self.geti(AL_SOURCE_DISTANCE_MODEL)
+            .and_then(DistanceModel::from_al)
+    }
+
+    /// Returns the playback state of this source.
+    pub fn state(&self) -> Result<SourceState, Error> {
+        self.geti(AL_SOURCE_STATE).and_then(|state| match state {
+            AL_INITIAL => Ok(SourceState::Initial),
+            AL_PLAYING => Ok(SourceState::Playing),
+            AL_PAUSED => Ok(SourceState::Paused),
+            AL_STOPPED => Ok(SourceState::Stopped),
+            _ => Err(Error::InvalidEnum),
+        })
+    }
+
+    /// Appends the named buffers to this source's queue.
+    pub(crate) fn queue_buffers(&self, buffers: &[ALuint]) -> Result<(), Error> {
+        let name = self.name;
+        let n = ALsizei::try_from(buffers.len()).map_err(|_| Error::InvalidValue)?;
+        self.context
+            .call(|| unsafe { alSourceQueueBuffers(name, n, buffers.as_ptr()) })
+    }
+
+    /// Removes processed buffers from this source's queue, storing their names in `buffers`.
+    pub(crate) fn unqueue_buffers(&self, buffers: &mut [ALuint]) -> Result<(), Error> {
+        let name = self.name;
+        let n = ALsizei::try_from(buffers.len()).map_err(|_| Error::InvalidValue)?;
+        self.context
+            .call(|| unsafe { alSourceUnqueueBuffers(name, n, buffers.as_mut_ptr()) })
+    }
+
+    /// Returns the number of buffers in this source's queue.
+    pub(crate) fn buffers_queued(&self) -> Result<usize, Error> {
+        self.geti(AL_BUFFERS_QUEUED).map(|n| n as usize)
+    }
+
+    /// Returns the number of queued buffers that have finished playing.
+    pub(crate) fn buffers_processed(&self) -> Result<usize, Error> {
+        self.geti(AL_BUFFERS_PROCESSED).map(|n| n as usize)
+    }
+
+    /// Collects the names of `sources`, which must share a context, and passes them to `f`.
+    fn batch<F>(sources: &[&Self], f: F) -> Result<(), Error>
+    where
+        F: FnOnce(&'a Context<'a>, ALsizei, *const ALuint) -> Result<(), Error>,
+    {
+        let context = match sources.first() {
+            Some(source) => source.context,
+            None => return Ok(()),
+        };
+        if sources.len() > MAX_BATCH_SOURCES {
+            return Err(Error::InvalidValue);
+        }
+        let mut names = [0; MAX_BATCH_SOURCES];
+        for (name, source) in names.iter_mut().zip(sources) {
+            if !ptr::eq(source.context, context) {
+                return Err(Error::InvalidOperation);
+            }
+            *name = source.name;
+        }
+        let n = ALsizei::try_from(sources.len()).map_err(|_| Error::InvalidValue)?;
+        f(context, n, names.as_ptr())
+    }
+
+    fn seti(&self, param: ALenum, value: ALint) -> Result<(), Error> {
+        let name = self.name;
+        self.context
+            .call(|| unsafe { alSourcei(name, param, value) })
+    }
+
+    fn getf(&self, param: ALenum) -> Result<ALfloat, Error> {
+        let name = self.name;
+        let mut value = 0.0;
+        self.context
+            .call(|| unsafe { alGetSourcef(name, param, &mut value) })
+            .map(|()| value)
+    }
+
+    fn geti(&self, param: ALenum) -> Result<ALint, Error> {
+        let name = self.name;
+        let mut value = 0;
+        self.context
+            .call(|| unsafe { alGetSourcei(name, param, &mut value) })
+            .map(|()| value)
+    }
+}
+
+impl<'a> Drop for Source<'a> {
+    fn drop(&mut self) {
+        let name = self.name;
+        let _ = self.context.call(|| unsafe { alDeleteSources(1, &name) });
+    }
+}
+
+/// Converts a duration into a number of frames at the given sample rate, rounding down.
+pub(crate) fn frames_from_duration(duration: Duration, frequency: u32) -> u64 {
+    let frequency = u64::from(frequency);
+    duration.as_secs() * frequency + u64::from(duration.subsec_nanos()) * frequency / 1_000_000_000
+}
+
+/// Converts a time in seconds reported by AL, clamping negative or invalid values to zero.
+fn duration_from_secs(secs: f64) -> Duration {
+    if secs.is_finite() && secs > 0.0 {
+        Duration::from_secs_f64(secs)
+    } else {
+        Duration::from_secs(0)
+    }
+}
+
+/// Converts a device clock time into the nanosecond count expected by AL.
+fn device_nanos(time: Duration) -> Result<ALint64SOFT, Error> {
+    ALint64SOFT::try_from(time.as_nanos()).map_err(|_| Error::InvalidValue)
+}
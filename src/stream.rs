@@ -0,0 +1,216 @@
+// Copyright (c) 2018 FaultyRAM
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the
+// MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at
+// your option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use bindgen_openal_sys::ALuint;
+use buffer::Buffer;
+use context::Context;
+use core::time::Duration;
+use error::Error;
+use format::Format;
+use source::{self, Source, SourceState};
+
+/// The number of buffers a `Stream` cycles through.
+const STREAM_BUFFERS: usize = 4;
+
+/// A producer of audio data that can be decoded incrementally.
+pub trait Decoder {
+    /// Returns the format of the decoded audio data.
+    fn format(&self) -> Format;
+
+    /// Returns the sample rate of the decoded audio data, in hertz.
+    fn frequency(&self) -> u32;
+
+    /// Decodes audio data into `buf`, returning the number of bytes written.
+    ///
+    /// Implementations must only write whole blocks (see `Format::block_size`). A return value of
+    /// zero indicates that the end of the audio data has been reached, so implementations should
+    /// return `Error::InvalidValue` rather than zero if `buf` is too small to hold a block.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error>;
+
+    /// Moves the decoder to the given frame, returning the frame it actually moved to.
+    ///
+    /// Decoders that can only seek to the start of a block may round down. The default
+    /// implementation returns `Error::InvalidOperation`, for decoders that can't seek.
+    fn seek(&mut self, frame: u64) -> Result<u64, Error> {
+        let _ = frame;
+        Err(Error::InvalidOperation)
+    }
+}
+
+#[derive(Debug)]
+/// A source that plays audio data as it is decoded, rather than all at once.
+///
+/// A `Stream` cycles a small number of buffers through its source's queue, refilling each one from
+/// its decoder after it has finished playing. `update` must be called regularly to keep the queue
+/// topped up.
+pub struct Stream<'a, D> {
+    source: Source<'a>,
+    buffers: [Buffer<'a>; STREAM_BUFFERS],
+    decoder: D,
+    scratch: &'a mut [u8],
+    played: u64,
+    playing: bool,
+    finished: bool,
+}
+
+impl<'a, D: Decoder> Stream<'a, D> {
+    /// Creates a new stream that plays audio data from `decoder`.
+    ///
+    /// `scratch` is used to hold decoded data before it is uploaded, and determines the size of
+    /// each queued buffer. It must be large enough to hold at least one block.
+    pub fn new(context: &'a Context<'a>, decoder: D, scratch: &'a mut [u8]) -> Result<Self, Error> {
+        if scratch.len() < decoder.format().block_size() {
+            return Err(Error::InvalidValue);
+        }
+        let mut stream = Self {
+            source: Source::new(context)?,
+            buffers: [
+                Buffer::new(context)?,
+                Buffer::new(context)?,
+                Buffer::new(context)?,
+                Buffer::new(context)?,
+            ],
+            decoder,
+            scratch,
+            played: 0,
+            playing: false,
+            finished: false,
+        };
+        for i in 0..STREAM_BUFFERS {
+            let name = stream.buffers[i].name();
+            if !stream.refill(name)? {
+                break;
+            }
+        }
+        Ok(stream)
+    }
+
+    /// Returns the source this stream plays through.
+    ///
+    /// This can be used to adjust source properties; playback should be controlled through the
+    /// stream itself.
+    pub fn source(&self) -> &Source<'a> {
+        &self.source
+    }
+
+    /// Returns a reference to the decoder this stream reads from.
+    pub fn decoder(&self) -> &D {
+        &self.decoder
+    }
+
+    /// Returns the playback position, as the time since the start of the stream.
+    ///
+    /// Unlike `Source::position`, this includes buffers that have already been played and
+    /// requeued.
+    pub fn position(&self) -> Result<Duration, Error> {
+        let frames = self.played + self.source.frame_position()?;
+        let frequency = u64::from(self.decoder.frequency());
+        if frequency == 0 {
+            return Ok(Duration::from_secs(0));
+        }
+        let nanos = frames % frequency * 1_000_000_000 / frequency;
+        Ok(Duration::new(frames / frequency, nanos as u32))
+    }
+
+    /// Moves the playback position to the given time from the start of the stream.
+    ///
+    /// Queued data is discarded and refilled from the new position, so this requires a decoder
+    /// that supports seeking. Playback continues from the new position if the stream was playing.
+    pub fn seek(&mut self, position: Duration) -> Result<(), Error> {
+        let frame = source::frames_from_duration(position, self.decoder.frequency());
+        let frame = self.decoder.seek(frame)?;
+        self.source.stop()?;
+        self.source.set_buffer(None)?;
+        self.played = frame;
+        self.finished = false;
+        for i in 0..STREAM_BUFFERS {
+            let name = self.buffers[i].name();
+            if !self.refill(name)? {
+                break;
+            }
+        }
+        if self.playing {
+            self.source.play()?;
+        }
+        Ok(())
+    }
+
+    /// Starts or resumes playback.
+    pub fn play(&mut self) -> Result<(), Error> {
+        self.source.play().map(|()| self.playing = true)
+    }
+
+    /// Pauses playback.
+    pub fn pause(&mut self) -> Result<(), Error> {
+        self.source.pause().map(|()| self.playing = false)
+    }
+
+    /// Stops playback.
+    pub fn stop(&mut self) -> Result<(), Error> {
+        self.source.stop().map(|()| self.playing = false)
+    }
+
+    /// Refills and requeues any buffers that have finished playing.
+    ///
+    /// If the source ran out of queued data while playing, it is restarted. Returns `false` once
+    /// the decoder has been exhausted and every queued buffer has finished playing.
+    pub fn update(&mut self) -> Result<bool, Error> {
+        let mut names = [0; STREAM_BUFFERS];
+        let processed = self.source.buffers_processed()?.min(STREAM_BUFFERS);
+        let names = &mut names[..processed];
+        self.source.unqueue_buffers(names)?;
+        for &name in names.iter() {
+            self.played += self.buffer(name)?.frames() as u64;
+        }
+        for &name in names.iter() {
+            if !self.refill(name)? {
+                break;
+            }
+        }
+        let queued = self.source.buffers_queued()?;
+        if self.playing && queued > 0 && self.source.state()? == SourceState::Stopped {
+            self.source.play()?;
+        }
+        Ok(!self.finished || queued > 0)
+    }
+
+    /// Fills the named buffer from the decoder and queues it, returning `false` at end of stream.
+    fn refill(&mut self, name: ALuint) -> Result<bool, Error> {
+        if self.finished {
+            return Ok(false);
+        }
+        let format = self.decoder.format();
+        let frequency = self.decoder.frequency();
+        let len = self.scratch.len() - self.scratch.len() % format.block_size();
+        let mut filled = 0;
+        while filled < len {
+            match self.decoder.read(&mut self.scratch[filled..len])? {
+                0 => break,
+                n => filled += n,
+            }
+        }
+        if filled == 0 {
+            self.finished = true;
+            return Ok(false);
+        }
+        self.buffers
+            .iter_mut()
+            .find(|buffer| buffer.name() == name)
+            .ok_or(Error::InvalidName)?
+            .set_raw_data(format, &self.scratch[..filled], frequency)?;
+        self.source.queue_buffers(&[name]).map(|()| true)
+    }
+
+    /// Returns the buffer with the given name, or `Error::InvalidName` if it isn't one of ours.
+    fn buffer(&self, name: ALuint) -> Result<&Buffer<'a>, Error> {
+        self.buffers
+            .iter()
+            .find(|buffer| buffer.name() == name)
+            .ok_or(Error::InvalidName)
+    }
+}
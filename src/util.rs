@@ -0,0 +1,31 @@
+// Copyright (c) 2018 FaultyRAM
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the
+// MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at
+// your option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::mem::size_of_val;
+use core::slice;
+use format::Sample;
+
+const MAX_C_STR_LEN: usize = 256;
+
+/// Calls `f` with a pointer to a nul-terminated copy of `s`.
+///
+/// Returns `None` if `s` contains a nul byte or is too long to copy.
+pub(crate) fn with_c_str<T, F: FnOnce(*const u8) -> T>(s: &str, f: F) -> Option<T> {
+    let bytes = s.as_bytes();
+    if bytes.len() >= MAX_C_STR_LEN || bytes.contains(&0) {
+        return None;
+    }
+    let mut buf = [0; MAX_C_STR_LEN];
+    buf[..bytes.len()].copy_from_slice(bytes);
+    Some(f(buf.as_ptr()))
+}
+
+/// Reinterprets a slice of samples as raw bytes.
+pub(crate) fn as_bytes<T: Sample>(samples: &[T]) -> &[u8] {
+    unsafe { slice::from_raw_parts(samples.as_ptr().cast(), size_of_val(samples)) }
+}
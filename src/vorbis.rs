@@ -0,0 +1,93 @@
+// Copyright (c) 2018 FaultyRAM
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the
+// MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at
+// your option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::fmt::{self, Debug, Formatter};
+use error::Error;
+use format::{ChannelLayout, Format, SampleType};
+use lewton::inside_ogg::OggStreamReader;
+use pcm::PcmQueue;
+use std::io::{Read, Seek};
+use stream::Decoder;
+
+/// A decoder for Ogg Vorbis audio data.
+///
+/// Vorbis streams with 1, 2, 4, 6, 7 or 8 channels are supported, and are decoded to 16-bit
+/// samples. Multichannel streams are reordered from Vorbis channel order to the order expected by
+/// `AL_EXT_MCFORMATS`.
+pub struct VorbisDecoder<R: Read + Seek> {
+    reader: OggStreamReader<R>,
+    format: Format,
+    queue: PcmQueue,
+}
+
+impl<R: Read + Seek> VorbisDecoder<R> {
+    /// Reads the Vorbis headers from `reader` and creates a decoder for the stream that follows.
+    pub fn new(reader: R) -> Result<Self, Error> {
+        let reader = OggStreamReader::new(reader).map_err(Error::Vorbis)?;
+        let channels = usize::from(reader.ident_hdr.audio_channels);
+        let layout =
+            ChannelLayout::from_channels(channels).ok_or(Error::UnsupportedChannels(channels))?;
+        Ok(Self {
+            reader,
+            format: Format::new(layout, SampleType::I16),
+            queue: PcmQueue::default(),
+        })
+    }
+}
+
+impl<R: Read + Seek> Debug for VorbisDecoder<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VorbisDecoder")
+            .field("format", &self.format)
+            .field("frequency", &self.reader.ident_hdr.audio_sample_rate)
+            .finish()
+    }
+}
+
+impl<R: Read + Seek> Decoder for VorbisDecoder<R> {
+    fn format(&self) -> Format {
+        self.format
+    }
+
+    fn frequency(&self) -> u32 {
+        self.reader.ident_hdr.audio_sample_rate
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        let reader = &mut self.reader;
+        let layout = self.format.layout;
+        self.queue.read(buf, layout.channels(), |samples| {
+            match reader.read_dec_packet_itl().map_err(Error::Vorbis)? {
+                Some(packet) => {
+                    *samples = packet;
+                    reorder(samples, layout);
+                    Ok(true)
+                }
+                None => Ok(false),
+            }
+        })
+    }
+}
+
+/// Reorders interleaved samples from Vorbis channel order to OpenAL channel order.
+fn reorder(samples: &mut [i16], layout: ChannelLayout) {
+    // For each OpenAL channel, the index of the corresponding Vorbis channel.
+    let map: &[usize] = match layout {
+        ChannelLayout::Surround51 => &[0, 2, 1, 5, 3, 4],
+        ChannelLayout::Surround61 => &[0, 2, 1, 6, 5, 3, 4],
+        ChannelLayout::Surround71 => &[0, 2, 1, 7, 5, 6, 3, 4],
+        _ => return,
+    };
+    let mut frame = [0; 8];
+    for chunk in samples.chunks_mut(map.len()) {
+        frame[..chunk.len()].copy_from_slice(chunk);
+        for (sample, &channel) in chunk.iter_mut().zip(map) {
+            *sample = frame[channel];
+        }
+    }
+}
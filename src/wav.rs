@@ -0,0 +1,600 @@
+// Copyright (c) 2018 FaultyRAM
+//
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or http://www.apache.org/licenses/LICENSE-2.0> or the
+// MIT license <LICENSE-MIT or http://opensource.org/licenses/MIT>, at
+// your option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[cfg(feature = "std")]
+use core::convert::TryFrom;
+use core::fmt::{self, Display, Formatter};
+use error::Error;
+use format::{ChannelLayout, Format, SampleType};
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+#[cfg(feature = "std")]
+use std::vec::Vec;
+use stream::Decoder;
+
+const WAVE_FORMAT_PCM: u16 = 0x0001;
+const WAVE_FORMAT_ADPCM: u16 = 0x0002;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 0x0003;
+const WAVE_FORMAT_ALAW: u16 = 0x0006;
+const WAVE_FORMAT_MULAW: u16 = 0x0007;
+const WAVE_FORMAT_IMA_ADPCM: u16 = 0x0011;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+// `WAVE_FORMAT_EXTENSIBLE` channel masks for each supported channel layout.
+const SPEAKERS_MONO: u32 = 0x004;
+const SPEAKERS_STEREO: u32 = 0x003;
+const SPEAKERS_REAR: u32 = 0x030;
+const SPEAKERS_QUAD: u32 = 0x033;
+const SPEAKERS_51_BACK: u32 = 0x03F;
+const SPEAKERS_51_SIDE: u32 = 0x60F;
+const SPEAKERS_61: u32 = 0x70F;
+const SPEAKERS_71: u32 = 0x63F;
+
+/// The coefficient table every Microsoft ADPCM file uses, which OpenAL assumes.
+const MSADPCM_COEFFICIENTS: [u8; 28] = [
+    0x00, 0x01, 0x00, 0x00, 0x00, 0x02, 0x00, 0xFF, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x40, 0x00,
+    0xF0, 0x00, 0x00, 0x00, 0xCC, 0x01, 0x30, 0xFF, 0x88, 0x01, 0x18, 0xFF,
+];
+
+/// The bytes shared by every `KSDATAFORMAT_SUBTYPE_*` GUID after the leading format tag.
+const SUBFORMAT_SUFFIX: [u8; 14] = [
+    0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71,
+];
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+/// An error encountered while parsing WAV data.
+pub enum WavError {
+    /// The data does not begin with a RIFF header.
+    NotRiff,
+    /// The RIFF form type is not `WAVE`.
+    NotWave,
+    /// A chunk extends past the end of the data.
+    Truncated,
+    /// There is no `fmt ` chunk before the `data` chunk.
+    MissingFormat,
+    /// There is no `data` chunk.
+    MissingData,
+    /// The `fmt ` chunk is malformed or internally inconsistent.
+    InvalidFormat,
+    /// The audio data uses an encoding with the given format tag, which is not supported.
+    UnsupportedEncoding(u16),
+    /// The audio data uses a supported encoding, but with an unsupported sample size.
+    UnsupportedBitDepth(u16),
+    /// The audio data has an unsupported number of channels.
+    UnsupportedChannels(u16),
+}
+
+impl Display for WavError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            WavError::NotRiff => f.write_str("not a RIFF file"),
+            WavError::NotWave => f.write_str("not a WAVE file"),
+            WavError::Truncated => f.write_str("WAV data is truncated"),
+            WavError::MissingFormat => f.write_str("WAV data has no format chunk"),
+            WavError::MissingData => f.write_str("WAV data has no data chunk"),
+            WavError::InvalidFormat => f.write_str("WAV format chunk is invalid"),
+            WavError::UnsupportedEncoding(tag) => {
+                write!(f, "unsupported WAV encoding {:#06x}", tag)
+            }
+            WavError::UnsupportedBitDepth(bits) => {
+                write!(f, "unsupported WAV sample size of {} bits", bits)
+            }
+            WavError::UnsupportedChannels(channels) => {
+                write!(f, "unsupported WAV channel count of {}", channels)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+/// Audio data parsed from, or to be written to, a WAV file.
+///
+/// Every `SampleType` is supported, in any `ChannelLayout` other than B-Format. Multichannel
+/// layouts are identified by a `WAVE_FORMAT_EXTENSIBLE` channel mask.
+pub struct Wav<'a> {
+    format: Format,
+    frequency: u32,
+    data: &'a [u8],
+}
+
+impl<'a> Wav<'a> {
+    /// Creates a `Wav` from audio data in the given format, such as to write it to a file.
+    ///
+    /// Any partial block at the end of `data` is ignored.
+    pub fn new(format: Format, frequency: u32, data: &'a [u8]) -> Self {
+        let len = data.len() - data.len() % format.block_size();
+        Self {
+            format,
+            frequency,
+            data: &data[..len],
+        }
+    }
+
+    /// Parses the contents of a WAV file.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, WavError> {
+        if bytes.len() < 8 || &bytes[..4] != b"RIFF" {
+            return Err(WavError::NotRiff);
+        }
+        if bytes.len() < 12 || &bytes[8..12] != b"WAVE" {
+            return Err(WavError::NotWave);
+        }
+        let mut chunks = &bytes[12..];
+        let mut format = None;
+        while chunks.len() >= 8 {
+            let size = read_u32(&chunks[4..8]) as usize;
+            let body = 8usize
+                .checked_add(size)
+                .and_then(|end| chunks.get(8..end))
+                .ok_or(WavError::Truncated)?;
+            match &chunks[..4] {
+                b"fmt " => format = Some(parse_format(body)?),
+                b"data" => {
+                    let (format, frequency) = format.ok_or(WavError::MissingFormat)?;
+                    let len = body.len() - body.len() % format.block_size();
+                    return Ok(Self {
+                        format,
+                        frequency,
+                        data: &body[..len],
+                    });
+                }
+                _ => (),
+            }
+            chunks = chunks.get(8 + size + (size & 1)..).unwrap_or(&[]);
+        }
+        Err(if format.is_some() {
+            WavError::MissingData
+        } else {
+            WavError::MissingFormat
+        })
+    }
+
+    /// Returns the format of the audio data.
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the sample rate of the audio data, in hertz.
+    pub fn frequency(&self) -> u32 {
+        self.frequency
+    }
+
+    /// Returns the raw audio data.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Returns a decoder that reads the audio data incrementally, for use with a `Stream`.
+    pub fn decoder(&self) -> WavDecoder<'a> {
+        WavDecoder {
+            wav: *self,
+            remaining: self.data,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    /// Writes the audio data as a WAV file.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let format = write_format(self.format, self.frequency);
+        let data_len = self.data.len() + (self.data.len() & 1);
+        let riff_len = u32::try_from(4 + 8 + format.len() + 8 + data_len)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "WAV data is too large"))?;
+        writer.write_all(b"RIFF")?;
+        writer.write_all(&riff_len.to_le_bytes())?;
+        writer.write_all(b"WAVEfmt ")?;
+        writer.write_all(&(format.len() as u32).to_le_bytes())?;
+        writer.write_all(&format)?;
+        writer.write_all(b"data")?;
+        writer.write_all(&(self.data.len() as u32).to_le_bytes())?;
+        writer.write_all(self.data)?;
+        if self.data.len() & 1 != 0 {
+            writer.write_all(&[0])?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+/// A decoder that reads audio data from a `Wav`.
+pub struct WavDecoder<'a> {
+    wav: Wav<'a>,
+    remaining: &'a [u8],
+}
+
+impl<'a> Decoder for WavDecoder<'a> {
+    fn format(&self) -> Format {
+        self.wav.format
+    }
+
+    fn frequency(&self) -> u32 {
+        self.wav.frequency
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        let block_size = self.wav.format.block_size();
+        if buf.len() < block_size && !self.remaining.is_empty() {
+            return Err(Error::InvalidValue);
+        }
+        let len = buf.len() - buf.len() % block_size;
+        let len = len.min(self.remaining.len());
+        let (head, tail) = self.remaining.split_at(len);
+        buf[..len].copy_from_slice(head);
+        self.remaining = tail;
+        Ok(len)
+    }
+
+    fn seek(&mut self, frame: u64) -> Result<u64, Error> {
+        let block_size = self.wav.format.block_size();
+        let block_frames = self.wav.format.sample_type.block_frames() as u64;
+        let blocks = (self.wav.data.len() / block_size) as u64;
+        let block = (frame / block_frames).min(blocks);
+        self.remaining = &self.wav.data[block as usize * block_size..];
+        Ok(block * block_frames)
+    }
+}
+
+/// Parses the body of a `fmt ` chunk into a format and sample rate.
+fn parse_format(body: &[u8]) -> Result<(Format, u32), WavError> {
+    if body.len() < 16 {
+        return Err(WavError::InvalidFormat);
+    }
+    let mut tag = read_u16(&body[0..2]);
+    let mut channel_mask = 0;
+    let channels = read_u16(&body[2..4]);
+    let frequency = read_u32(&body[4..8]);
+    let block_align = read_u16(&body[12..14]);
+    let bits = read_u16(&body[14..16]);
+    if tag == WAVE_FORMAT_EXTENSIBLE {
+        if body.len() < 40 {
+            return Err(WavError::InvalidFormat);
+        }
+        if body[26..40] != SUBFORMAT_SUFFIX {
+            return Err(WavError::UnsupportedEncoding(tag));
+        }
+        channel_mask = read_u32(&body[20..24]);
+        tag = read_u16(&body[24..26]);
+        // ADPCM formats keep their block length where `WAVE_FORMAT_EXTENSIBLE` keeps the number
+        // of valid bits, so they can't be described this way.
+        if tag == WAVE_FORMAT_IMA_ADPCM || tag == WAVE_FORMAT_ADPCM {
+            return Err(WavError::UnsupportedEncoding(tag));
+        }
+    }
+    // Fall back to the channel count if the mask is missing, unrecognised or inconsistent.
+    let layout = match channel_mask {
+        SPEAKERS_MONO => Some(ChannelLayout::Mono),
+        SPEAKERS_STEREO => Some(ChannelLayout::Stereo),
+        SPEAKERS_REAR => Some(ChannelLayout::Rear),
+        SPEAKERS_QUAD => Some(ChannelLayout::Quad),
+        SPEAKERS_51_BACK | SPEAKERS_51_SIDE => Some(ChannelLayout::Surround51),
+        SPEAKERS_61 => Some(ChannelLayout::Surround61),
+        SPEAKERS_71 => Some(ChannelLayout::Surround71),
+        _ => None,
+    }
+    .filter(|layout| layout.channels() == usize::from(channels))
+    .or_else(|| ChannelLayout::from_channels(usize::from(channels)))
+    .ok_or(WavError::UnsupportedChannels(channels))?;
+    let sample_type = match (tag, bits) {
+        (WAVE_FORMAT_PCM, 8) => SampleType::U8,
+        (WAVE_FORMAT_PCM, 16) => SampleType::I16,
+        (WAVE_FORMAT_IEEE_FLOAT, 32) => SampleType::F32,
+        (WAVE_FORMAT_IEEE_FLOAT, 64) => SampleType::F64,
+        (WAVE_FORMAT_ALAW, 8) => SampleType::ALaw,
+        (WAVE_FORMAT_MULAW, 8) => SampleType::MuLaw,
+        (WAVE_FORMAT_IMA_ADPCM, 4) if body.len() >= 20 => SampleType::Ima4(read_u16(&body[18..20])),
+        (WAVE_FORMAT_ADPCM, 4) if body.len() >= 50 => {
+            if read_u16(&body[20..22]) != 7 || body[22..50] != MSADPCM_COEFFICIENTS {
+                return Err(WavError::UnsupportedEncoding(tag));
+            }
+            SampleType::MsAdpcm(read_u16(&body[18..20]))
+        }
+        (WAVE_FORMAT_IMA_ADPCM, 4) | (WAVE_FORMAT_ADPCM, 4) => return Err(WavError::InvalidFormat),
+        (WAVE_FORMAT_PCM, _)
+        | (WAVE_FORMAT_IEEE_FLOAT, _)
+        | (WAVE_FORMAT_ALAW, _)
+        | (WAVE_FORMAT_MULAW, _)
+        | (WAVE_FORMAT_IMA_ADPCM, _)
+        | (WAVE_FORMAT_ADPCM, _) => return Err(WavError::UnsupportedBitDepth(bits)),
+        _ => return Err(WavError::UnsupportedEncoding(tag)),
+    };
+    let format = Format::new(layout, sample_type);
+    if frequency == 0 || usize::from(block_align) != format.block_size() {
+        return Err(WavError::InvalidFormat);
+    }
+    Ok((format, frequency))
+}
+
+#[cfg(feature = "std")]
+/// Builds the body of a `fmt ` chunk describing a format and sample rate.
+fn write_format(format: Format, frequency: u32) -> Vec<u8> {
+    let channels = format.layout.channels() as u16;
+    let block_align = format.block_size() as u16;
+    let block_frames = format.sample_type.block_frames();
+    let byte_rate = (u64::from(frequency) * u64::from(block_align) / block_frames as u64) as u32;
+    let (tag, bits): (u16, u16) = match format.sample_type {
+        SampleType::U8 => (WAVE_FORMAT_PCM, 8),
+        SampleType::I16 => (WAVE_FORMAT_PCM, 16),
+        SampleType::F32 => (WAVE_FORMAT_IEEE_FLOAT, 32),
+        SampleType::F64 => (WAVE_FORMAT_IEEE_FLOAT, 64),
+        SampleType::MuLaw => (WAVE_FORMAT_MULAW, 8),
+        SampleType::ALaw => (WAVE_FORMAT_ALAW, 8),
+        SampleType::Ima4(_) => (WAVE_FORMAT_IMA_ADPCM, 4),
+        SampleType::MsAdpcm(_) => (WAVE_FORMAT_ADPCM, 4),
+    };
+    let channel_mask = match format.layout {
+        ChannelLayout::Mono | ChannelLayout::Stereo => None,
+        ChannelLayout::Rear => Some(SPEAKERS_REAR),
+        ChannelLayout::Quad => Some(SPEAKERS_QUAD),
+        ChannelLayout::Surround51 => Some(SPEAKERS_51_SIDE),
+        ChannelLayout::Surround61 => Some(SPEAKERS_61),
+        ChannelLayout::Surround71 => Some(SPEAKERS_71),
+        ChannelLayout::BFormat2D(_) | ChannelLayout::BFormat3D(_) => Some(0),
+    };
+    let mut body = Vec::with_capacity(50);
+    let outer_tag = if channel_mask.is_some() && bits >= 8 {
+        WAVE_FORMAT_EXTENSIBLE
+    } else {
+        tag
+    };
+    body.extend_from_slice(&outer_tag.to_le_bytes());
+    body.extend_from_slice(&channels.to_le_bytes());
+    body.extend_from_slice(&frequency.to_le_bytes());
+    body.extend_from_slice(&byte_rate.to_le_bytes());
+    body.extend_from_slice(&block_align.to_le_bytes());
+    body.extend_from_slice(&bits.to_le_bytes());
+    match format.sample_type {
+        _ if outer_tag == WAVE_FORMAT_EXTENSIBLE => {
+            body.extend_from_slice(&22u16.to_le_bytes());
+            body.extend_from_slice(&bits.to_le_bytes());
+            body.extend_from_slice(&channel_mask.unwrap_or(0).to_le_bytes());
+            body.extend_from_slice(&tag.to_le_bytes());
+            body.extend_from_slice(&SUBFORMAT_SUFFIX);
+        }
+        SampleType::U8 | SampleType::I16 => (),
+        SampleType::Ima4(frames) => {
+            body.extend_from_slice(&2u16.to_le_bytes());
+            body.extend_from_slice(&frames.to_le_bytes());
+        }
+        SampleType::MsAdpcm(frames) => {
+            body.extend_from_slice(&32u16.to_le_bytes());
+            body.extend_from_slice(&frames.to_le_bytes());
+            body.extend_from_slice(&7u16.to_le_bytes());
+            body.extend_from_slice(&MSADPCM_COEFFICIENTS);
+        }
+        _ => body.extend_from_slice(&0u16.to_le_bytes()),
+    }
+    body
+}
+
+fn read_u16(bytes: &[u8]) -> u16 {
+    u16::from(bytes[0]) | u16::from(bytes[1]) << 8
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from(read_u16(&bytes[0..2])) | u32::from(read_u16(&bytes[2..4])) << 16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds the body of a plain `fmt ` chunk.
+    fn format(tag: u16, channels: u16, frequency: u32, block_align: u16, bits: u16) -> [u8; 16] {
+        let mut body = [0; 16];
+        body[0..2].copy_from_slice(&tag.to_le_bytes());
+        body[2..4].copy_from_slice(&channels.to_le_bytes());
+        body[4..8].copy_from_slice(&frequency.to_le_bytes());
+        body[8..12].copy_from_slice(&(frequency * u32::from(block_align)).to_le_bytes());
+        body[12..14].copy_from_slice(&block_align.to_le_bytes());
+        body[14..16].copy_from_slice(&bits.to_le_bytes());
+        body
+    }
+
+    /// Builds the body of a `WAVE_FORMAT_EXTENSIBLE` `fmt ` chunk.
+    fn extensible(channels: u16, block_align: u16, bits: u16, mask: u32, tag: u16) -> [u8; 40] {
+        let mut body = [0; 40];
+        body[..16].copy_from_slice(&format(
+            WAVE_FORMAT_EXTENSIBLE,
+            channels,
+            48_000,
+            block_align,
+            bits,
+        ));
+        body[16..18].copy_from_slice(&22u16.to_le_bytes());
+        body[18..20].copy_from_slice(&bits.to_le_bytes());
+        body[20..24].copy_from_slice(&mask.to_le_bytes());
+        body[24..26].copy_from_slice(&tag.to_le_bytes());
+        body[26..40].copy_from_slice(&SUBFORMAT_SUFFIX);
+        body
+    }
+
+    /// Writes a WAV file made of the given chunks into `buf`, returning its length.
+    fn riff(buf: &mut [u8], chunks: &[(&[u8; 4], &[u8])]) -> usize {
+        buf[..4].copy_from_slice(b"RIFF");
+        buf[8..12].copy_from_slice(b"WAVE");
+        let mut len = 12;
+        for &(id, body) in chunks {
+            buf[len..len + 4].copy_from_slice(id);
+            buf[len + 4..len + 8].copy_from_slice(&(body.len() as u32).to_le_bytes());
+            buf[len + 8..len + 8 + body.len()].copy_from_slice(body);
+            len += 8 + body.len() + (body.len() & 1);
+        }
+        buf[4..8].copy_from_slice(&(len as u32 - 8).to_le_bytes());
+        len
+    }
+
+    fn parse(chunks: &[(&[u8; 4], &[u8])]) -> Result<(Format, u32, usize), WavError> {
+        let mut buf = [0; 256];
+        let len = riff(&mut buf, chunks);
+        Wav::parse(&buf[..len]).map(|wav| (wav.format(), wav.frequency(), wav.data().len()))
+    }
+
+    #[test]
+    fn parse_pcm() {
+        let fmt = format(WAVE_FORMAT_PCM, 1, 8000, 1, 8);
+        assert_eq!(
+            parse(&[(b"fmt ", &fmt), (b"data", &[0x80, 0x7F, 0x81])]),
+            Ok((Format::new(ChannelLayout::Mono, SampleType::U8), 8000, 3))
+        );
+        let fmt = format(WAVE_FORMAT_PCM, 2, 44_100, 4, 16);
+        assert_eq!(
+            parse(&[(b"fmt ", &fmt), (b"data", &[0; 10])]),
+            Ok((
+                Format::new(ChannelLayout::Stereo, SampleType::I16),
+                44_100,
+                8
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_float_and_mulaw() {
+        let fmt = format(WAVE_FORMAT_IEEE_FLOAT, 1, 48_000, 4, 32);
+        assert_eq!(
+            parse(&[(b"fmt ", &fmt), (b"data", &[0; 8])]),
+            Ok((Format::new(ChannelLayout::Mono, SampleType::F32), 48_000, 8))
+        );
+        let fmt = format(WAVE_FORMAT_MULAW, 2, 8000, 2, 8);
+        assert_eq!(
+            parse(&[(b"fmt ", &fmt), (b"data", &[0xFF; 4])]),
+            Ok((
+                Format::new(ChannelLayout::Stereo, SampleType::MuLaw),
+                8000,
+                4
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_extensible() {
+        let fmt = extensible(6, 12, 16, SPEAKERS_51_BACK, WAVE_FORMAT_PCM);
+        assert_eq!(
+            parse(&[(b"fmt ", &fmt), (b"data", &[0; 24])]),
+            Ok((
+                Format::new(ChannelLayout::Surround51, SampleType::I16),
+                48_000,
+                24
+            ))
+        );
+        let fmt = extensible(2, 8, 32, SPEAKERS_REAR, WAVE_FORMAT_IEEE_FLOAT);
+        assert_eq!(
+            parse(&[(b"fmt ", &fmt), (b"data", &[0; 8])]),
+            Ok((Format::new(ChannelLayout::Rear, SampleType::F32), 48_000, 8))
+        );
+        // A mask that doesn't match the channel count is ignored.
+        let fmt = extensible(4, 8, 16, SPEAKERS_STEREO, WAVE_FORMAT_PCM);
+        assert_eq!(
+            parse(&[(b"fmt ", &fmt), (b"data", &[0; 8])]),
+            Ok((Format::new(ChannelLayout::Quad, SampleType::I16), 48_000, 8))
+        );
+        let fmt = extensible(1, 36, 4, SPEAKERS_MONO, WAVE_FORMAT_IMA_ADPCM);
+        assert_eq!(
+            parse(&[(b"fmt ", &fmt), (b"data", &[0; 36])]),
+            Err(WavError::UnsupportedEncoding(WAVE_FORMAT_IMA_ADPCM))
+        );
+    }
+
+    #[test]
+    fn parse_skips_padded_chunks() {
+        let fmt = format(WAVE_FORMAT_PCM, 1, 8000, 1, 8);
+        assert_eq!(
+            parse(&[
+                (b"LIST", &[1, 2, 3]),
+                (b"fmt ", &fmt),
+                (b"fact", &[4]),
+                (b"data", &[0x80; 5]),
+            ]),
+            Ok((Format::new(ChannelLayout::Mono, SampleType::U8), 8000, 5))
+        );
+    }
+
+    #[test]
+    fn parse_truncated() {
+        let fmt = format(WAVE_FORMAT_PCM, 1, 8000, 1, 8);
+        let mut buf = [0; 64];
+        let len = riff(&mut buf, &[(b"fmt ", &fmt)]);
+        assert_eq!(Wav::parse(&buf[..len - 6]), Err(WavError::Truncated));
+
+        let len = riff(&mut buf, &[(b"fmt ", &fmt), (b"data", &[0x80; 8])]);
+        assert_eq!(Wav::parse(&buf[..len - 4]), Err(WavError::Truncated));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let fmt = format(WAVE_FORMAT_PCM, 1, 8000, 1, 8);
+        let data: &[u8] = &[0x80; 4];
+        assert_eq!(Wav::parse(b"RIFX\0\0\0\0WAVE"), Err(WavError::NotRiff));
+        assert_eq!(Wav::parse(b"RIFF\0\0\0\0AVI "), Err(WavError::NotWave));
+        assert_eq!(
+            parse(&[(b"data", data), (b"fmt ", &fmt)]),
+            Err(WavError::MissingFormat)
+        );
+        assert_eq!(parse(&[(b"fmt ", &fmt)]), Err(WavError::MissingData));
+        assert_eq!(parse(&[]), Err(WavError::MissingFormat));
+        assert_eq!(
+            parse(&[(b"fmt ", &fmt[..14]), (b"data", data)]),
+            Err(WavError::InvalidFormat)
+        );
+        let fmt = format(WAVE_FORMAT_PCM, 1, 8000, 2, 8);
+        assert_eq!(
+            parse(&[(b"fmt ", &fmt), (b"data", data)]),
+            Err(WavError::InvalidFormat)
+        );
+        let fmt = format(0x0050, 1, 8000, 1, 8);
+        assert_eq!(
+            parse(&[(b"fmt ", &fmt), (b"data", data)]),
+            Err(WavError::UnsupportedEncoding(0x0050))
+        );
+        let fmt = format(WAVE_FORMAT_PCM, 1, 8000, 3, 24);
+        assert_eq!(
+            parse(&[(b"fmt ", &fmt), (b"data", data)]),
+            Err(WavError::UnsupportedBitDepth(24))
+        );
+        let fmt = format(WAVE_FORMAT_PCM, 3, 8000, 6, 16);
+        assert_eq!(
+            parse(&[(b"fmt ", &fmt), (b"data", data)]),
+            Err(WavError::UnsupportedChannels(3))
+        );
+    }
+
+    #[test]
+    fn decoder_rejects_short_buffers() {
+        let wav = Wav::new(
+            Format::new(ChannelLayout::Stereo, SampleType::I16),
+            44_100,
+            &[1; 8],
+        );
+        let mut decoder = wav.decoder();
+        match decoder.read(&mut [0; 2]) {
+            Err(Error::InvalidValue) => {}
+            result => panic!("{:?}", result),
+        }
+        let mut buf = [0; 6];
+        assert_eq!(decoder.read(&mut buf).ok(), Some(4));
+        assert_eq!(decoder.read(&mut buf).ok(), Some(4));
+        assert_eq!(decoder.read(&mut buf[..2]).ok(), Some(0));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn write_round_trip() {
+        let data = [0x5A; 152];
+        for &format in &[
+            Format::new(ChannelLayout::Mono, SampleType::U8),
+            Format::new(ChannelLayout::Stereo, SampleType::I16),
+            Format::new(ChannelLayout::Surround71, SampleType::F32),
+            Format::new(ChannelLayout::Surround51, SampleType::MuLaw),
+            Format::new(ChannelLayout::Rear, SampleType::F64),
+            Format::new(ChannelLayout::Mono, SampleType::Ima4(65)),
+            Format::new(ChannelLayout::Stereo, SampleType::MsAdpcm(64)),
+        ] {
+            let wav = Wav::new(format, 22_050, &data);
+            let mut bytes = Vec::new();
+            wav.write_to(&mut bytes).unwrap();
+            assert_eq!(Wav::parse(&bytes), Ok(wav));
+        }
+    }
+}